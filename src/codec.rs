@@ -0,0 +1,183 @@
+//! Packing of byte strings and 256-bit integers into the u32 limbs MASM procedures work on.
+//!
+//! MASM hash procedures take their input as u32 limbs, one limb per field element:
+//! `sha256` expects big-endian limbs, `keccak256` little-endian ones. 256-bit integers (ECDSA
+//! scalars and field elements, message hashes) are given as 8 u32 limbs, least significant limb
+//! first, and are passed to the MASM as two words whose limbs are reversed.
+
+use alloc::vec::Vec;
+use thiserror::Error;
+
+use miden_client::{Felt, Word};
+use miden_objects::vm::AdviceMap;
+
+use crate::common::prepare_felt_vec;
+
+/// Represents errors that can occur while decoding limbs from field elements.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CodecError {
+    #[error("element {0} does not fit into a u32 limb")]
+    /// A field element is larger than `u32::MAX`.
+    NotU32(u64),
+    #[error("{limbs} limbs cannot hold {byte_len} bytes")]
+    /// The number of limbs does not match the expected byte length.
+    LengthMismatch { limbs: usize, byte_len: usize },
+}
+
+// BYTES <-> LIMBS
+// ================================================================================================
+
+/// Packs bytes into big-endian u32 limbs, right-padding the last limb with zero bytes.
+pub fn bytes_to_be_limbs(bytes: &[u8]) -> Vec<u32> {
+    bytes
+        .chunks(4)
+        .map(|chunk| {
+            let mut limb = [0u8; 4];
+            limb[..chunk.len()].copy_from_slice(chunk);
+            u32::from_be_bytes(limb)
+        })
+        .collect()
+}
+
+/// Packs bytes into little-endian u32 limbs, right-padding the last limb with zero bytes.
+pub fn bytes_to_le_limbs(bytes: &[u8]) -> Vec<u32> {
+    bytes
+        .chunks(4)
+        .map(|chunk| {
+            let mut limb = [0u8; 4];
+            limb[..chunk.len()].copy_from_slice(chunk);
+            u32::from_le_bytes(limb)
+        })
+        .collect()
+}
+
+/// Unpacks `byte_len` bytes from big-endian u32 limbs, dropping the padding of the last limb.
+pub fn be_limbs_to_bytes(limbs: &[u32], byte_len: usize) -> Result<Vec<u8>, CodecError> {
+    check_limb_count(limbs.len(), byte_len)?;
+    let mut bytes: Vec<u8> = limbs.iter().flat_map(|limb| limb.to_be_bytes()).collect();
+    bytes.truncate(byte_len);
+    Ok(bytes)
+}
+
+/// Unpacks `byte_len` bytes from little-endian u32 limbs, dropping the padding of the last limb.
+pub fn le_limbs_to_bytes(limbs: &[u32], byte_len: usize) -> Result<Vec<u8>, CodecError> {
+    check_limb_count(limbs.len(), byte_len)?;
+    let mut bytes: Vec<u8> = limbs.iter().flat_map(|limb| limb.to_le_bytes()).collect();
+    bytes.truncate(byte_len);
+    Ok(bytes)
+}
+
+// LIMBS <-> FELTS
+// ================================================================================================
+
+/// Converts u32 limbs into field elements, one limb per element.
+pub fn limbs_to_felts(limbs: &[u32]) -> Vec<Felt> {
+    limbs.iter().map(|&limb| Felt::from(limb)).collect()
+}
+
+/// Converts field elements back into u32 limbs, failing on elements larger than `u32::MAX`.
+pub fn felts_to_limbs(felts: &[Felt]) -> Result<Vec<u32>, CodecError> {
+    felts
+        .iter()
+        .map(|felt| u32::try_from(felt.as_int()).map_err(|_| CodecError::NotU32(felt.as_int())))
+        .collect()
+}
+
+/// Packs bytes into big-endian u32 limbs held by field elements, as expected by `sha256`.
+pub fn bytes_to_be_felts(bytes: &[u8]) -> Vec<Felt> {
+    limbs_to_felts(&bytes_to_be_limbs(bytes))
+}
+
+/// Packs bytes into little-endian u32 limbs held by field elements, as expected by `keccak256`.
+pub fn bytes_to_le_felts(bytes: &[u8]) -> Vec<Felt> {
+    limbs_to_felts(&bytes_to_le_limbs(bytes))
+}
+
+/// Unpacks `byte_len` bytes from big-endian u32 limbs held by field elements.
+pub fn be_felts_to_bytes(felts: &[Felt], byte_len: usize) -> Result<Vec<u8>, CodecError> {
+    be_limbs_to_bytes(&felts_to_limbs(felts)?, byte_len)
+}
+
+/// Unpacks `byte_len` bytes from little-endian u32 limbs held by field elements.
+pub fn le_felts_to_bytes(felts: &[Felt], byte_len: usize) -> Result<Vec<u8>, CodecError> {
+    le_limbs_to_bytes(&felts_to_limbs(felts)?, byte_len)
+}
+
+/// Packs bytes into the length-prefixed `[byte_len, limb_0, limb_1, ...]` big-endian form used
+/// to reveal preimages of any length through the advice map.
+pub fn length_prefixed_be_felts(bytes: &[u8]) -> Vec<Felt> {
+    let mut felts = vec![Felt::new(bytes.len() as u64)];
+    felts.extend(bytes_to_be_felts(bytes));
+    felts
+}
+
+/// Packs bytes into the length-prefixed `[byte_len, limb_0, limb_1, ...]` little-endian form used
+/// to reveal preimages of any length to `keccak256` through the advice map.
+pub fn length_prefixed_le_felts(bytes: &[u8]) -> Vec<Felt> {
+    let mut felts = vec![Felt::new(bytes.len() as u64)];
+    felts.extend(bytes_to_le_felts(bytes));
+    felts
+}
+
+/// Unpacks bytes from the length-prefixed `[byte_len, limb_0, limb_1, ...]` big-endian form.
+pub fn length_prefixed_be_felts_to_bytes(felts: &[Felt]) -> Result<Vec<u8>, CodecError> {
+    let Some((byte_len, limbs)) = felts.split_first() else {
+        return Err(CodecError::LengthMismatch {
+            limbs: 0,
+            byte_len: 0,
+        });
+    };
+    be_felts_to_bytes(limbs, byte_len.as_int() as usize)
+}
+
+// 256-BIT INTEGERS
+// ================================================================================================
+
+/// Splits a 256-bit integer, given as 8 u32 limbs least significant first, into the two words
+/// the MASM u256 procedures load: `[l7, l6, l5, l4]` then `[l3, l2, l1, l0]`.
+pub fn u256_to_words(limbs: &[u32; 8]) -> [Word; 2] {
+    let felt = |i: usize| Felt::from(limbs[i]);
+    [
+        Word::from([felt(7), felt(6), felt(5), felt(4)]),
+        Word::from([felt(3), felt(2), felt(1), felt(0)]),
+    ]
+}
+
+/// Joins the two words produced by [`u256_to_words`] back into 8 u32 limbs.
+pub fn words_to_u256(words: &[Word; 2]) -> Result<[u32; 8], CodecError> {
+    let mut limbs = [0u32; 8];
+    for (i, felt) in words[0].iter().chain(words[1].iter()).enumerate() {
+        limbs[7 - i] =
+            u32::try_from(felt.as_int()).map_err(|_| CodecError::NotU32(felt.as_int()))?;
+    }
+    Ok(limbs)
+}
+
+/// Inserts a 256-bit integer into `advice_map` under the keys `key` and `key + 1`, in the order
+/// the MASM u256 procedures read them.
+pub fn insert_u256(advice_map: &mut AdviceMap, key: u64, limbs: &[u32; 8]) {
+    let [high, low] = u256_to_words(limbs);
+    advice_map.insert(prepare_felt_vec(key).into(), high.to_vec());
+    advice_map.insert(prepare_felt_vec(key + 1).into(), low.to_vec());
+}
+
+// DIGESTS
+// ================================================================================================
+
+/// Returns whether `felts` hold `digest` as big-endian u32 limbs, the form of `sha256` output.
+pub fn digest_matches_be_felts(digest: &[u8], felts: &[Felt]) -> bool {
+    felts.len() * 4 == digest.len() && felts == bytes_to_be_felts(digest).as_slice()
+}
+
+/// Returns whether `felts` hold `digest` as little-endian u32 limbs, the form of `keccak256`
+/// output.
+pub fn digest_matches_le_felts(digest: &[u8], felts: &[Felt]) -> bool {
+    felts.len() * 4 == digest.len() && felts == bytes_to_le_felts(digest).as_slice()
+}
+
+fn check_limb_count(limbs: usize, byte_len: usize) -> Result<(), CodecError> {
+    if limbs != byte_len.div_ceil(4) {
+        return Err(CodecError::LengthMismatch { limbs, byte_len });
+    }
+    Ok(())
+}
@@ -4,8 +4,9 @@ use miden_client::{
         Account, AccountBuilder, AccountId, AccountStorageMode, AccountType, NetworkId, StorageMap,
         StorageSlot,
     },
-    asset::{Asset, FungibleAsset, TokenSymbol},
+    asset::{Asset, FungibleAsset, NonFungibleAsset, NonFungibleAssetDetails, TokenSymbol},
     auth::AuthSecretKey,
+    block::BlockNumber,
     builder::ClientBuilder,
     crypto::SecretKey,
     keystore::FilesystemKeyStore,
@@ -21,6 +22,7 @@ use miden_lib::account::{
     faucets::BasicFungibleFaucet,
     wallets::BasicWallet,
 };
+use miden_lib::note::create_p2id_note;
 use miden_lib::transaction::TransactionKernel;
 use miden_objects::{
     Hasher, NoteError,
@@ -30,6 +32,8 @@ use miden_objects::{
 };
 use rand::{RngCore, rngs::StdRng};
 use serde::de::value::Error;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
 use std::{
     fs,
     path::{Path, PathBuf},
@@ -38,7 +42,17 @@ use std::{
 };
 use tokio::time::sleep;
 
-use crate::constants::{MULTISIG_CODE_PATH, NETWORK_ID, SIGNER_WEIGHTS, THRESHOLD, TOTAL_WEIGHT};
+use crate::codec::{
+    bytes_to_be_felts, bytes_to_le_felts, length_prefixed_be_felts, length_prefixed_le_felts,
+};
+use crate::constants::{
+    KECCAK256_PREIMAGE_KEY_SLOT, MINT_NFT_SCRIPT_PATH, MULTISIG_CODE_PATH, NETWORK_ID,
+    NFT_ASSET_KEY_SLOT, NFT_FAUCET_CODE_PATH, NFT_FAUCET_LIBRARY_PATH, NFT_NOTE_METADATA_KEY_SLOT,
+    NFT_RECIPIENT_KEY_SLOT, NOTE_ASSETS_CODE_PATH, NOTE_ASSETS_LIBRARY_PATH,
+    SHA256_PREIMAGE_KEY_SLOT, SIGNER_WEIGHTS, THRESHOLD, TOTAL_WEIGHT, WIDE_MATH_CODE_PATH,
+    WIDE_MATH_LIBRARY_PATH,
+};
+use crate::ecdsa::eth_address_storage_slots;
 
 type Client = MidenClient<FilesystemKeyStore<rand::prelude::StdRng>>;
 
@@ -76,13 +90,22 @@ pub fn create_library(
     library_path: &str,
 ) -> Result<Library, Box<dyn std::error::Error>> {
     let assembler: Assembler = TransactionKernel::assembler().with_debug_mode(true);
+    create_library_with_assembler(assembler, account_code, library_path)
+}
+
+// Compiles a library with the given assembler, e.g. one linking other libraries
+pub fn create_library_with_assembler(
+    assembler: Assembler,
+    account_code: String,
+    library_path: &str,
+) -> Result<Library, Box<dyn std::error::Error>> {
     let source_manager = Arc::new(DefaultSourceManager::default());
     let module = Module::parser(ModuleKind::Library).parse_str(
         LibraryPath::new(library_path)?,
         account_code,
         &source_manager,
     )?;
-    let library = assembler.clone().assemble_library([module])?;
+    let library = assembler.assemble_library([module])?;
     Ok(library)
 }
 
@@ -218,6 +241,98 @@ pub async fn create_basic_faucet(
     Ok(account)
 }
 
+// Creates a non-fungible faucet exposing `nft_faucet::mint_to_note`, owned by a new RpoFalcon512
+// key: the auth component stores the owner public key in slot 0 and only transactions signed by
+// the owner, added to `keystore`, can mint
+pub async fn create_nft_faucet(
+    client: &mut Client,
+    keystore: FilesystemKeyStore<StdRng>,
+) -> Result<(Account, SecretKey), ClientError> {
+    let mut init_seed = [0u8; 32];
+    client.rng().fill_bytes(&mut init_seed);
+    let key_pair = SecretKey::with_rng(client.rng());
+
+    let account_code = fs::read_to_string(Path::new(NFT_FAUCET_CODE_PATH)).unwrap();
+    let assembler: Assembler = TransactionKernel::assembler().with_debug_mode(true);
+    let nft_faucet_component = AccountComponent::compile(account_code, assembler, vec![])
+        .unwrap()
+        .with_supported_type(AccountType::NonFungibleFaucet);
+
+    let (account, seed) = AccountBuilder::new(init_seed)
+        .account_type(AccountType::NonFungibleFaucet)
+        .storage_mode(AccountStorageMode::Public)
+        .with_auth_component(AuthRpoFalcon512::new(key_pair.public_key().clone()))
+        .with_component(nft_faucet_component)
+        .build()
+        .unwrap();
+    client.add_account(&account, Some(seed), false).await?;
+    keystore
+        .add_key(&AuthSecretKey::RpoFalcon512(key_pair.clone()))
+        .unwrap();
+
+    Ok((account, key_pair))
+}
+
+// Mints a non-fungible asset carrying `data` from `faucet` into a P2ID note for `target`.
+//
+// Returns the minted asset together with the P2ID note holding it.
+pub async fn mint_non_fungible_asset(
+    client: &mut Client,
+    faucet: &Account,
+    target: AccountId,
+    data: Vec<u8>,
+) -> Result<(NonFungibleAsset, Note), ClientError> {
+    let details = NonFungibleAssetDetails::new(faucet.id().prefix(), data).unwrap();
+    let asset = NonFungibleAsset::new(&details).unwrap();
+
+    let note = create_p2id_note(
+        faucet.id(),
+        target,
+        vec![asset.into()],
+        NoteType::Public,
+        ZERO,
+        client.rng(),
+    )
+    .unwrap();
+
+    let script_code = fs::read_to_string(Path::new(MINT_NFT_SCRIPT_PATH)).unwrap();
+    let account_code = fs::read_to_string(Path::new(NFT_FAUCET_CODE_PATH)).unwrap();
+    let library = create_library(account_code, NFT_FAUCET_LIBRARY_PATH).unwrap();
+    let tx_script = create_tx_script(script_code, Some(library)).unwrap();
+
+    let metadata = note.metadata();
+    let mut advice_map = AdviceMap::default();
+    advice_map.insert(
+        prepare_felt_vec(NFT_ASSET_KEY_SLOT as u64).into(),
+        Word::from(asset).to_vec(),
+    );
+    advice_map.insert(
+        prepare_felt_vec(NFT_RECIPIENT_KEY_SLOT as u64).into(),
+        note.recipient().digest().to_vec(),
+    );
+    advice_map.insert(
+        prepare_felt_vec(NFT_NOTE_METADATA_KEY_SLOT as u64).into(),
+        vec![
+            Felt::new(metadata.execution_hint().into()),
+            Felt::from(metadata.note_type()),
+            metadata.aux(),
+            Felt::from(metadata.tag()),
+        ],
+    );
+
+    let tx_request = TransactionRequestBuilder::new()
+        .custom_script(tx_script)
+        .extend_advice_map(advice_map)
+        .expected_output_recipients(vec![note.recipient().clone()])
+        .build()
+        .unwrap();
+
+    let tx_result = client.new_transaction(faucet.id(), tx_request).await?;
+    client.submit_transaction(tx_result).await?;
+
+    Ok((asset, note))
+}
+
 // Creates basic account
 pub async fn create_basic_account(
     client: &mut Client,
@@ -501,6 +616,15 @@ pub async fn build_and_submit_tx(
     Ok(())
 }
 
+// Formats `word` as the `a.b.c.d` immediate of a MASM `push` instruction, keeping the word
+// element order on the stack.
+pub fn word_to_masm(word: Word) -> String {
+    word.iter()
+        .map(|felt| felt.as_int().to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
 pub fn prepare_felt_vec(element: u64) -> [Felt; 4] {
     [Felt::new(element), ZERO, ZERO, ZERO]
 }
@@ -633,12 +757,133 @@ pub async fn setup_accounts_and_faucets(
     Ok((accounts, faucets))
 }
 
+// Mints `amount` tokens of `faucet` to `account` and consumes the minted note, so the tokens
+// live in the account vault.
+pub async fn fund_account(
+    client: &mut Client,
+    faucet: &Account,
+    account: &Account,
+    amount: u64,
+) -> Result<(), ClientError> {
+    let asset = FungibleAsset::new(faucet.id(), amount).unwrap();
+    let tx_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(asset, account.id(), NoteType::Public, client.rng())
+        .unwrap();
+
+    let tx_exec = client.new_transaction(faucet.id(), tx_request).await?;
+    client.submit_transaction(tx_exec.clone()).await?;
+
+    let minted_note = match tx_exec.created_notes().get_note(0) {
+        OutputNote::Full(n) => n.clone(),
+        _ => panic!("Expected OutputNote::Full, got something else"),
+    };
+
+    wait_for_notes(client, account, 1).await?;
+    client.sync_state().await?;
+
+    let consume_req = TransactionRequestBuilder::new()
+        .authenticated_input_notes([(minted_note.id(), None)])
+        .build()
+        .unwrap();
+    let tx_exec = client.new_transaction(account.id(), consume_req).await?;
+    client.submit_transaction(tx_exec).await?;
+    client.sync_state().await?;
+
+    Ok(())
+}
+
+// Builds a public gift note holding `assets`, claimable by whoever knows `secret`.
+//
+// `assets` may bundle any mix of fungible and non-fungible assets; gift.masm moves every one of
+// them into the claimant's vault.
 pub fn create_gift_note_recallable(
     creator: AccountId,
-    offered_asset: Asset,
+    assets: NoteAssets,
     secret: [Felt; 4],
     serial_num: [Felt; 4],
 ) -> Result<Note, NoteError> {
+    create_gift_note(creator, assets, secret, serial_num, NoteType::Public)
+}
+
+// Builds a public gift note holding `assets`, claimable by whoever knows `secret` until block
+// `expiry`. From `expiry` on, the secret no longer opens the gift and `creator` can reclaim it by
+// consuming the note without note args.
+pub fn create_expiring_gift_note(
+    creator: AccountId,
+    assets: NoteAssets,
+    secret: [Felt; 4],
+    serial_num: [Felt; 4],
+    expiry: BlockNumber,
+) -> Result<Note, NoteError> {
+    create_gift_note_with_script(
+        creator,
+        assets,
+        secret,
+        serial_num,
+        NoteType::Public,
+        Some(expiry),
+        gift_note_script(),
+    )
+}
+
+// Builds a gift note of the given `note_type`.
+//
+// Private gifts only put the note commitment on-chain; the full note has to reach the recipient
+// off-chain (see `crate::private_gift::PrivateGift`).
+pub fn create_gift_note(
+    creator: AccountId,
+    assets: NoteAssets,
+    secret: [Felt; 4],
+    serial_num: [Felt; 4],
+    note_type: NoteType,
+) -> Result<Note, NoteError> {
+    let note = create_gift_note_with_script(
+        creator,
+        assets,
+        secret,
+        serial_num,
+        note_type,
+        None,
+        gift_note_script(),
+    )?;
+
+    println!(
+        "inputlen: {:?}, NoteInputs: {:?}",
+        note.recipient().inputs().num_values(),
+        note.recipient().inputs().values()
+    );
+    println!("tag: {:?}", note.metadata().tag());
+    println!("aux: {:?}", note.metadata().aux());
+    println!("note type: {:?}", note.metadata().note_type());
+    println!("hint: {:?}", note.metadata().execution_hint());
+    println!("recipient: {:?}", note.recipient().digest());
+
+    Ok(note)
+}
+
+// Compiles the note_assets.masm library shared by the notes paying their assets to the consumer
+pub fn note_assets_library() -> Library {
+    let code = fs::read_to_string(Path::new(NOTE_ASSETS_CODE_PATH)).unwrap();
+    create_library(code, NOTE_ASSETS_LIBRARY_PATH).unwrap()
+}
+
+// Compiles the wide_math.masm library, computing a * b / c on 128 bits for any amounts
+pub fn wide_math_library() -> Library {
+    let code = fs::read_to_string(Path::new(WIDE_MATH_CODE_PATH)).unwrap();
+    create_library(code, WIDE_MATH_LIBRARY_PATH).unwrap()
+}
+
+// Compiles a note script using note_assets.masm, which is linked statically into the script
+pub fn compile_note_script_with_assets(note_code: String) -> NoteScript {
+    ScriptBuilder::new(true)
+        .with_statically_linked_library(&note_assets_library())
+        .unwrap()
+        .compile_note_script(note_code)
+        .unwrap()
+}
+
+// Compiles gift.masm, so that many gift notes can share one script
+pub fn gift_note_script() -> NoteScript {
     let manifest_dir = env!("CARGO_MANIFEST_DIR");
     let path: PathBuf = [manifest_dir, "masm", "notes", "gift.masm"]
         .iter()
@@ -647,25 +892,36 @@ pub fn create_gift_note_recallable(
     let note_code = fs::read_to_string(&path)
         .unwrap_or_else(|err| panic!("Error reading {}: {}", path.display(), err));
 
-    let assembler = TransactionKernel::assembler().with_debug_mode(true);
-
-    let program = assembler.clone().assemble_program(note_code).unwrap();
-    let note_script = NoteScript::new(program);
-
-    let note_type = NoteType::Public;
+    compile_note_script_with_assets(note_code)
+}
 
-    let gift_tag = NoteTag::for_public_use_case(0, 0, NoteExecutionMode::Local)?;
+// Builds a gift note of the given `note_type` running `note_script`, compiled by
+// `gift_note_script`. A gift with an `expiry` can only be opened before that block, and reclaimed
+// by `creator` from that block on.
+pub fn create_gift_note_with_script(
+    creator: AccountId,
+    assets: NoteAssets,
+    secret: [Felt; 4],
+    serial_num: [Felt; 4],
+    note_type: NoteType,
+    expiry: Option<BlockNumber>,
+    note_script: NoteScript,
+) -> Result<Note, NoteError> {
+    let gift_tag = match note_type {
+        NoteType::Public => NoteTag::for_public_use_case(0, 0, NoteExecutionMode::Local)?,
+        _ => NoteTag::for_local_use_case(0, 0)?,
+    };
 
     let mut secret_vals = vec![secret[0], secret[1], secret[2], secret[3]];
-    println!("secret_vals: {:?}", secret_vals);
     // Prepend 4 zero elements to match the expected input format for the hash
     secret_vals.splice(0..0, Word::default().iter().cloned());
-    println!("gift creation - secret_vals: {:?}", secret_vals);
     let digest = Hasher::hash_elements(&secret_vals);
-    println!("digest: {:?}", digest);
-    println!("digest hex: {:?}", digest.to_hex());
 
-    let inputs = NoteInputs::new(digest.to_vec())?;
+    // a reclaim height of 0 means that the gift never expires
+    let reclaim_height = expiry.map_or(ZERO, |expiry| Felt::from(expiry.as_u32()));
+    let mut inputs = digest.to_vec();
+    inputs.push(reclaim_height);
+    let inputs = NoteInputs::new(inputs)?;
 
     let aux = Felt::new(0);
 
@@ -678,22 +934,9 @@ pub fn create_gift_note_recallable(
         aux,
     )?;
 
-    let assets = NoteAssets::new(vec![offered_asset])?;
-    let recipient = NoteRecipient::new(serial_num.into(), note_script.clone(), inputs.clone());
-    let note = Note::new(assets.clone(), metadata, recipient.clone());
+    let recipient = NoteRecipient::new(serial_num.into(), note_script, inputs);
 
-    println!(
-        "inputlen: {:?}, NoteInputs: {:?}",
-        inputs.num_values(),
-        inputs.values()
-    );
-    println!("tag: {:?}", note.metadata().tag());
-    println!("aux: {:?}", note.metadata().aux());
-    println!("note type: {:?}", note.metadata().note_type());
-    println!("hint: {:?}", note.metadata().execution_hint());
-    println!("recipient: {:?}", note.recipient().digest());
-
-    Ok(note)
+    Ok(Note::new(assets, metadata, recipient))
 }
 
 pub fn create_sha256_note(
@@ -709,9 +952,7 @@ pub fn create_sha256_note(
     let note_code = fs::read_to_string(&path)
         .unwrap_or_else(|err| panic!("Error reading {}: {}", path.display(), err));
 
-    let assembler = TransactionKernel::assembler().with_debug_mode(true);
-    let program = assembler.clone().assemble_program(note_code).unwrap();
-    let note_script = NoteScript::new(program);
+    let note_script = compile_note_script_with_assets(note_code);
     let note_type = NoteType::Public;
 
     let gift_tag = NoteTag::for_public_use_case(0, 0, NoteExecutionMode::Local)?;
@@ -744,6 +985,112 @@ pub fn create_sha256_note(
     Ok(note)
 }
 
+// Builds a SHA-256 hash-lock note for `preimage`, of any length.
+//
+// Returns the note together with the advice map revealing `preimage` to sha256.masm; the advice
+// map has to be added to the consuming transaction.
+pub fn create_sha256_note_from_preimage(
+    creator: AccountId,
+    preimage: &[u8],
+    serial_num: [Felt; 4],
+) -> Result<(Note, AdviceMap), NoteError> {
+    let note = create_sha256_note(creator, sha256_digest_inputs(preimage), serial_num)?;
+    Ok((note, sha256_preimage_advice(preimage)))
+}
+
+// Returns the SHA-256 digest of `preimage` as the 8 big-endian u32 words sha256.masm expects as
+// note inputs.
+pub fn sha256_digest_inputs(preimage: &[u8]) -> Vec<Felt> {
+    bytes_to_be_felts(&Sha256::digest(preimage))
+}
+
+// Builds the advice map revealing `preimage` to sha256.masm.
+//
+// The preimage is stored under key `0` as `[byte_len, word_0, word_1, ...]`, every word holding
+// 4 bytes in big-endian order.
+pub fn sha256_preimage_advice(preimage: &[u8]) -> AdviceMap {
+    let value = length_prefixed_be_felts(preimage);
+
+    let mut advice_map = AdviceMap::default();
+    advice_map.insert(
+        prepare_felt_vec(SHA256_PREIMAGE_KEY_SLOT as u64).into(),
+        value,
+    );
+    advice_map
+}
+
+// Builds a Keccak-256 hash-lock note expecting the digest words `bytes` (see
+// `keccak256_digest_inputs`).
+pub fn create_keccak256_note(
+    creator: AccountId,
+    bytes: Vec<Felt>,
+    serial_num: [Felt; 4],
+) -> Result<Note, NoteError> {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let path: PathBuf = [manifest_dir, "masm", "notes", "keccak256.masm"]
+        .iter()
+        .collect();
+
+    let note_code = fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("Error reading {}: {}", path.display(), err));
+
+    let note_script = compile_note_script_with_assets(note_code);
+
+    let tag = NoteTag::for_public_use_case(0, 0, NoteExecutionMode::Local)?;
+    let inputs = NoteInputs::new(bytes)?;
+    let metadata = NoteMetadata::new(
+        creator,
+        NoteType::Public,
+        tag,
+        NoteExecutionHint::always(),
+        Felt::new(0),
+    )?;
+
+    let recipient = NoteRecipient::new(serial_num.into(), note_script, inputs);
+    let note = Note::new(NoteAssets::new(vec![])?, metadata, recipient);
+
+    println!("recipient: {:?}", note.recipient().digest());
+
+    Ok(note)
+}
+
+// Builds a Keccak-256 hash-lock note for `preimage`, of any length, e.g. a bytes32 secret
+// committed to by an EVM contract.
+//
+// Returns the note together with the advice map revealing `preimage` to keccak256.masm.
+pub fn create_keccak256_note_from_preimage(
+    creator: AccountId,
+    preimage: &[u8],
+    serial_num: [Felt; 4],
+) -> Result<(Note, AdviceMap), NoteError> {
+    let note = create_keccak256_note(creator, keccak256_digest_inputs(preimage), serial_num)?;
+    Ok((note, keccak256_preimage_advice(preimage)))
+}
+
+// Returns the Keccak-256 digest of `preimage` as the 8 little-endian u32 words keccak256.masm
+// expects as note inputs.
+//
+// The digest `0xc5d24601...` is packed as `[0x0146d2c5, ...]`: the i-th word holds bytes
+// `4i..4i+4` of the digest, least significant byte first.
+pub fn keccak256_digest_inputs(preimage: &[u8]) -> Vec<Felt> {
+    bytes_to_le_felts(&Keccak256::digest(preimage))
+}
+
+// Builds the advice map revealing `preimage` to keccak256.masm.
+//
+// The preimage is stored under key `0` as `[byte_len, word_0, word_1, ...]`, every word holding
+// 4 bytes in little-endian order.
+pub fn keccak256_preimage_advice(preimage: &[u8]) -> AdviceMap {
+    let value = length_prefixed_le_felts(preimage);
+
+    let mut advice_map = AdviceMap::default();
+    advice_map.insert(
+        prepare_felt_vec(KECCAK256_PREIMAGE_KEY_SLOT as u64).into(),
+        value,
+    );
+    advice_map
+}
+
 pub async fn create_no_auth_component() -> Result<AccountComponent, Error> {
     let assembler: Assembler = TransactionKernel::assembler().with_debug_mode(true);
     let no_auth_code = fs::read_to_string(Path::new("./masm/auth/no_auth.masm")).unwrap();
@@ -779,9 +1126,79 @@ pub async fn create_no_auth_faucet(
     Ok(new_account)
 }
 
+pub async fn create_auth_faucet(
+    client: &mut Client,
+    keystore: FilesystemKeyStore<StdRng>,
+    token_symbol: &str,
+    max_supply: u64,
+    decimals: u8,
+    storage_mode: AccountStorageMode,
+) -> Result<(Account, SecretKey), ClientError> {
+    let mut init_seed = [0u8; 32];
+    client.rng().fill_bytes(&mut init_seed);
+
+    let key_pair = SecretKey::with_rng(client.rng());
+    let symbol = TokenSymbol::new(token_symbol).unwrap();
+
+    let (new_account, seed) = AccountBuilder::new(init_seed)
+        .account_type(AccountType::FungibleFaucet)
+        .storage_mode(storage_mode)
+        .with_auth_component(AuthRpoFalcon512::new(key_pair.public_key()))
+        .with_component(BasicFungibleFaucet::new(symbol, decimals, Felt::new(max_supply)).unwrap())
+        .build()
+        .unwrap();
+    client.add_account(&new_account, Some(seed), false).await?;
+    keystore
+        .add_key(&AuthSecretKey::RpoFalcon512(key_pair.clone()))
+        .unwrap();
+    Ok((new_account, key_pair))
+}
+
+// Mints `amount` tokens of `faucet_id` straight into a gift note opened by `secret`.
+//
+// The faucet is the sender of the gift, so no funded intermediate wallet is needed. Works with
+// any faucet exposing the basic fungible faucet interface, with or without authentication.
+pub async fn mint_gift_note(
+    client: &mut Client,
+    faucet_id: AccountId,
+    amount: u64,
+    secret: [Felt; 4],
+) -> Result<Note, ClientError> {
+    let serial_num = client.rng().inner_mut().draw_word();
+
+    let gift_note = create_gift_note_recallable(
+        faucet_id,
+        NoteAssets::new(vec![FungibleAsset::new(faucet_id, amount).unwrap().into()]).unwrap(),
+        secret,
+        serial_num.into(),
+    )
+    .unwrap();
+
+    // the basic fungible faucet distributes the minted asset into every own output note
+    let tx_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(gift_note.clone())])
+        .build()
+        .unwrap();
+    let tx_result = client.new_transaction(faucet_id, tx_request).await?;
+    client.submit_transaction(tx_result).await?;
+
+    Ok(gift_note)
+}
+
 pub async fn create_evm_account(
     client: &mut Client,
     storage_mode: AccountStorageMode,
+) -> Result<Account, ClientError> {
+    // no public key hashes to the zero address
+    create_evm_account_with_address(client, storage_mode, &[0u8; 20]).await
+}
+
+// Creates an EVM account whose `verify_eth_address` accepts signatures of the owner of
+// `address`.
+pub async fn create_evm_account_with_address(
+    client: &mut Client,
+    storage_mode: AccountStorageMode,
+    address: &[u8; 20],
 ) -> Result<Account, ClientError> {
     let mut init_seed = [0u8; 32];
     client.rng().fill_bytes(&mut init_seed);
@@ -790,9 +1207,13 @@ pub async fn create_evm_account(
     let account_code = fs::read_to_string(Path::new("./masm/accounts/evm.masm")).unwrap();
     let assembler: Assembler = TransactionKernel::assembler().with_debug_mode(true);
 
-    let evm_component = AccountComponent::compile(account_code.clone(), assembler.clone(), vec![])
-        .unwrap()
-        .with_supports_all_types();
+    let evm_component = AccountComponent::compile(
+        account_code.clone(),
+        assembler.clone(),
+        eth_address_storage_slots(address),
+    )
+    .unwrap()
+    .with_supports_all_types();
 
     let (new_account, seed) = AccountBuilder::new(init_seed)
         .account_type(AccountType::FungibleFaucet)
@@ -14,6 +14,15 @@ pub const TOTAL_WEIGHT_SLOT: usize = 2;
 pub const SIGNERS_SLOT: usize = 3;
 pub const MESSAGE_HASH_SLOT: usize = 4;
 
+// zoro pool storage slots, slot 0 holds the auth public key
+pub const POOL_STATE_SLOT: u8 = 1;
+pub const POOL_ADMIN_SLOT: u8 = 2;
+pub const LP_SHARES_SLOT: u8 = 3;
+pub const POOL_ASSETS_SLOT: u8 = 4;
+
+// evm storage slots, slots 0 and 1 hold the Ethereum address
+pub const ETH_ACTION_NONCE_SLOT: u8 = 2;
+
 // error
 pub const INVALID_WEIGHT: usize = 100;
 
@@ -27,6 +36,28 @@ pub const NEW_SIGNER_WEIGHT_KEY_SLOT: usize = 1;
 // advice map location for remove signer
 pub const SIGNER_TO_REMOVE_KEY_SLOT: usize = 0;
 
+// advice map location for mint nft
+pub const NFT_ASSET_KEY_SLOT: usize = 0;
+pub const NFT_RECIPIENT_KEY_SLOT: usize = 1;
+pub const NFT_NOTE_METADATA_KEY_SLOT: usize = 2;
+
+// advice map location for sha256 hash-lock preimage
+pub const SHA256_PREIMAGE_KEY_SLOT: usize = 0;
+
+// advice map location for keccak256 hash-lock preimage
+pub const KECCAK256_PREIMAGE_KEY_SLOT: usize = 0;
+
+// advice map location for verify ecdsa, every u256 takes this key and the next one
+pub const ECDSA_SIGNATURE_S_KEY_SLOT: u64 = 0;
+pub const ECDSA_SIGNATURE_R_KEY_SLOT: u64 = 2;
+pub const ECDSA_MESSAGE_HASH_KEY_SLOT: u64 = 4;
+pub const ECDSA_PUBKEY_Z_KEY_SLOT: u64 = 6;
+pub const ECDSA_PUBKEY_Y_KEY_SLOT: u64 = 8;
+pub const ECDSA_PUBKEY_X_KEY_SLOT: u64 = 10;
+
+// advice map location of the message authorized with verify_eth_action
+pub const ETH_ACTION_MESSAGE_KEY_SLOT: u64 = 12;
+
 // file location
 pub const MULTISIG_CODE_PATH: &str = "./masm/accounts/multisig.masm";
 pub const EVM_CODE_PATH: &str = "./masm/accounts/evm.masm";
@@ -34,8 +65,20 @@ pub const CHANGE_THRESHOLD_SCRIPT_PATH: &str = "./masm/scripts/change_threshold.
 pub const ADD_SIGNER_SCRIPT_PATH: &str = "./masm/scripts/add_signer.masm";
 pub const REMOVE_SIGNER_SCRIPT_PATH: &str = "./masm/scripts/remove_signer.masm";
 pub const VERIFY_ECDSA_SCRIPT_PATH: &str = "./masm/scripts/verify_ecdsa.masm";
+pub const VERIFY_ETH_ADDRESS_SCRIPT_PATH: &str = "./masm/scripts/verify_eth_address.masm";
+pub const VERIFY_ETH_ACTION_SCRIPT_PATH: &str = "./masm/scripts/verify_eth_action.masm";
+pub const ETH_MESSAGE_HASHES_SCRIPT_PATH: &str = "./masm/scripts/eth_message_hashes.masm";
+pub const NFT_FAUCET_CODE_PATH: &str = "./masm/accounts/nft_faucet.masm";
+pub const MINT_NFT_SCRIPT_PATH: &str = "./masm/scripts/mint_nft.masm";
+pub const ZORO_POOL_CODE_PATH: &str = "./masm/accounts/zoro_pool.masm";
+pub const NOTE_ASSETS_CODE_PATH: &str = "./masm/lib/note_assets.masm";
+pub const WIDE_MATH_CODE_PATH: &str = "./masm/lib/wide_math.masm";
 pub const LIBRARY_PATH: &str = "external_contract::multisig_contract";
 pub const EVM_LIBRARY_PATH: &str = "external_contract::evm_contract";
+pub const NFT_FAUCET_LIBRARY_PATH: &str = "external_contract::nft_faucet";
+pub const ZORO_POOL_LIBRARY_PATH: &str = "external_contract::zoro_pool";
+pub const NOTE_ASSETS_LIBRARY_PATH: &str = "lib::note_assets";
+pub const WIDE_MATH_LIBRARY_PATH: &str = "lib::wide_math";
 
 // miden client
 pub const SYNC_STATE_WAIT_TIME: u64 = 7;
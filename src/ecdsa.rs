@@ -0,0 +1,179 @@
+//! Advice inputs of the secp256k1 ECDSA verification of the EVM component.
+//!
+//! `evm_contract::verify_signature` reads the public key, the message hash and the signature from
+//! the advice map as 256-bit integers in the Montgomery form of the MASM secp256k1 procedures: the
+//! public key as projective coordinates over the base field, the message hash and the signature
+//! scalars over the scalar field, each multiplied by `2^256`.
+//!
+//! `evm_contract::verify_eth_address` takes the same inputs, with the public key recovered from
+//! an Ethereum signature, and checks it against the address stored by the EVM component.
+
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use k256::elliptic_curve::ops::Reduce;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::{FieldBytes, FieldElement, Scalar, U256};
+use sha3::{Digest, Keccak256};
+use thiserror::Error;
+
+use miden_client::account::StorageSlot;
+use miden_client::{Felt, Word, ZERO};
+use miden_objects::vm::AdviceMap;
+
+use crate::codec::{bytes_to_le_limbs, insert_u256};
+use crate::constants::{
+    ECDSA_MESSAGE_HASH_KEY_SLOT, ECDSA_PUBKEY_X_KEY_SLOT, ECDSA_PUBKEY_Y_KEY_SLOT,
+    ECDSA_PUBKEY_Z_KEY_SLOT, ECDSA_SIGNATURE_R_KEY_SLOT, ECDSA_SIGNATURE_S_KEY_SLOT,
+};
+
+/// `2^256 mod p`, the Montgomery form of 1 over the base field.
+const FIELD_MONTGOMERY_ONE: u64 = 0x1000003d1;
+
+/// Represents errors that can occur while reading Ethereum signatures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum EcdsaError {
+    #[error("r or s of the signature is zero or not lower than the group order")]
+    /// The signature scalars are out of range.
+    InvalidSignature,
+    #[error("invalid recovery id {0}")]
+    /// The recovery id `v` is none of 0, 1, 27 and 28.
+    InvalidRecoveryId(u8),
+    #[error("no public key can be recovered from the signature")]
+    /// The signature does not belong to any public key.
+    RecoveryFailed,
+}
+
+/// Inputs of `evm_contract::verify_signature`, as 8 u32 limbs least significant first, in
+/// Montgomery form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EcdsaAdviceInputs {
+    /// X coordinate of the public key.
+    pub public_key_x: [u32; 8],
+    /// Y coordinate of the public key.
+    pub public_key_y: [u32; 8],
+    /// Z coordinate of the public key.
+    pub public_key_z: [u32; 8],
+    /// Message hash reduced modulo the group order.
+    pub message_hash: [u32; 8],
+    /// `r` part of the signature.
+    pub signature_r: [u32; 8],
+    /// `s` part of the signature.
+    pub signature_s: [u32; 8],
+}
+
+impl EcdsaAdviceInputs {
+    /// Converts a k256 signature of the 32-byte `prehash` under `verifying_key`.
+    ///
+    /// The prehash is read as a big-endian integer and reduced modulo the group order, as in
+    /// `VerifyingKey::verify_prehash`. The public key is given with `Z = 1`.
+    pub fn from_k256(
+        verifying_key: &VerifyingKey,
+        signature: &Signature,
+        prehash: &[u8; 32],
+    ) -> Self {
+        let (public_key_x, public_key_y) = ecdsa_public_key(verifying_key);
+
+        // 2^256 mod n, i.e. (2^256 - 1 mod n) + 1
+        let scalar_one = <Scalar as Reduce<U256>>::reduce(U256::MAX) + Scalar::ONE;
+        let message_hash = <Scalar as Reduce<U256>>::reduce_bytes(&FieldBytes::from(*prehash));
+        let (r, s) = signature.split_scalars();
+
+        Self {
+            public_key_x,
+            public_key_y,
+            public_key_z: field_to_limbs(FieldElement::from_u64(FIELD_MONTGOMERY_ONE)),
+            message_hash: scalar_to_limbs(message_hash * scalar_one),
+            signature_r: scalar_to_limbs(*r * scalar_one),
+            signature_s: scalar_to_limbs(*s * scalar_one),
+        }
+    }
+
+    /// Converts a 65-byte Ethereum signature `r || s || v` of the 32-byte `prehash`, recovering
+    /// the public key of the signer from the recovery id `v`.
+    ///
+    /// `v` is accepted both as 0 or 1 and as 27 or 28.
+    pub fn from_eth_signature(
+        signature: &[u8; 65],
+        prehash: &[u8; 32],
+    ) -> Result<Self, EcdsaError> {
+        let v = signature[64];
+        let recovery_id = RecoveryId::from_byte(if v >= 27 { v.wrapping_sub(27) } else { v })
+            .ok_or(EcdsaError::InvalidRecoveryId(v))?;
+        let signature =
+            Signature::from_slice(&signature[..64]).map_err(|_| EcdsaError::InvalidSignature)?;
+        let verifying_key = VerifyingKey::recover_from_prehash(prehash, &signature, recovery_id)
+            .map_err(|_| EcdsaError::RecoveryFailed)?;
+
+        Ok(Self::from_k256(&verifying_key, &signature, prehash))
+    }
+
+    /// Returns the advice map read by `evm_contract::verify_signature`.
+    pub fn to_advice_map(&self) -> AdviceMap {
+        let mut advice_map = AdviceMap::default();
+        self.extend_advice_map(&mut advice_map);
+        advice_map
+    }
+
+    /// Inserts the inputs into `advice_map` under the keys read by
+    /// `evm_contract::verify_signature`.
+    pub fn extend_advice_map(&self, advice_map: &mut AdviceMap) {
+        insert_u256(advice_map, ECDSA_SIGNATURE_S_KEY_SLOT, &self.signature_s);
+        insert_u256(advice_map, ECDSA_SIGNATURE_R_KEY_SLOT, &self.signature_r);
+        insert_u256(advice_map, ECDSA_MESSAGE_HASH_KEY_SLOT, &self.message_hash);
+        insert_u256(advice_map, ECDSA_PUBKEY_Z_KEY_SLOT, &self.public_key_z);
+        insert_u256(advice_map, ECDSA_PUBKEY_Y_KEY_SLOT, &self.public_key_y);
+        insert_u256(advice_map, ECDSA_PUBKEY_X_KEY_SLOT, &self.public_key_x);
+    }
+}
+
+/// Returns the X and Y coordinates of `verifying_key` in Montgomery form, the projective
+/// coordinates of the key with `Z = 1`.
+pub fn ecdsa_public_key(verifying_key: &VerifyingKey) -> ([u32; 8], [u32; 8]) {
+    let point = verifying_key.as_affine().to_encoded_point(false);
+    // the encoding of a valid verifying key is never the identity nor compressed
+    let x = FieldElement::from_bytes(point.x().unwrap()).unwrap();
+    let y = FieldElement::from_bytes(point.y().unwrap()).unwrap();
+    let field_one = FieldElement::from_u64(FIELD_MONTGOMERY_ONE);
+
+    (field_to_limbs(x * field_one), field_to_limbs(y * field_one))
+}
+
+/// Returns the Ethereum address of `verifying_key`: the last 20 bytes of the keccak256 digest of
+/// its uncompressed encoding without prefix.
+pub fn eth_address(verifying_key: &VerifyingKey) -> [u8; 20] {
+    let point = verifying_key.as_affine().to_encoded_point(false);
+    let digest = Keccak256::digest(&point.as_bytes()[1..]);
+    digest[12..].try_into().unwrap()
+}
+
+/// Returns the storage slots of the EVM component holding `address`, followed by the action
+/// nonce of `evm_contract::verify_eth_action`, starting at 0.
+///
+/// The address is stored as the little-endian u32 limbs `d3..d7` of the keccak256 digest, as
+/// `[d6, d5, d4, d3]` and `[0, 0, 0, d7]`.
+pub fn eth_address_storage_slots(address: &[u8; 20]) -> Vec<StorageSlot> {
+    let limb = |i: usize| {
+        Felt::from(u32::from_le_bytes(
+            address[4 * i..4 * i + 4].try_into().unwrap(),
+        ))
+    };
+    vec![
+        StorageSlot::Value(Word::from([limb(3), limb(2), limb(1), limb(0)])),
+        StorageSlot::Value(Word::from([ZERO, ZERO, ZERO, limb(4)])),
+        StorageSlot::Value(Word::from([ZERO; 4])),
+    ]
+}
+
+fn field_to_limbs(element: FieldElement) -> [u32; 8] {
+    be_bytes_to_limbs(element.normalize().to_bytes())
+}
+
+fn scalar_to_limbs(scalar: Scalar) -> [u32; 8] {
+    be_bytes_to_limbs(scalar.to_bytes())
+}
+
+// k256 encodes integers big-endian, the MASM takes the least significant limb first
+fn be_bytes_to_limbs(bytes: FieldBytes) -> [u32; 8] {
+    let mut le_bytes = bytes;
+    le_bytes.reverse();
+    bytes_to_le_limbs(&le_bytes).try_into().unwrap()
+}
@@ -0,0 +1,160 @@
+//! Miden accounts controlled by secp256k1 keys.
+//!
+//! The ECDSA auth component stores the public key of an Ethereum wallet and authenticates a
+//! transaction with a signature of that key over the transaction summary. As the summary is only
+//! known once the transaction has run, a transaction is executed twice: first without signature,
+//! which stops with the summary to sign, then with the signature in the advice map.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use std::fs;
+use std::path::PathBuf;
+use thiserror::Error;
+
+use k256::ecdsa::signature::hazmat::PrehashSigner;
+use k256::ecdsa::{Signature, SigningKey, VerifyingKey};
+
+use miden_client::account::{AccountBuilder, AccountId, AccountType, StorageSlot};
+use miden_client::auth::TransactionAuthenticator;
+use miden_client::transaction::{TransactionExecutorError, TransactionRequest, TransactionResult};
+use miden_client::{Client, ClientError, Felt, Word};
+use miden_lib::account::wallets::BasicWallet;
+use miden_lib::transaction::{TransactionEvent, TransactionKernel};
+use miden_objects::account::AccountComponent;
+use miden_objects::transaction::TransactionSummary;
+
+use crate::codec::u256_to_words;
+use crate::ecdsa::{EcdsaAdviceInputs, ecdsa_public_key};
+
+/// Represents errors that can occur while authenticating transactions with a secp256k1 key.
+#[derive(Debug, Error)]
+pub enum EcdsaAuthError {
+    #[error("transaction was executed without waiting for a signature")]
+    /// The transaction did not stop for a signature, the account is not ECDSA authenticated.
+    NotSignatureRequest,
+    #[error("client error: {0}")]
+    /// The client failed to execute the transaction.
+    Client(#[from] ClientError),
+}
+
+/// Builds the ECDSA auth component for `verifying_key`.
+pub fn ecdsa_auth_component(verifying_key: &VerifyingKey) -> AccountComponent {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let path: PathBuf = [manifest_dir, "masm", "auth", "ecdsa_auth.masm"]
+        .iter()
+        .collect();
+
+    let auth_code: String = fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("Error reading {}: {}", path.display(), err))
+        .replace(
+            "{{AUTH_UNAUTHORIZED_EVENT}}",
+            &(TransactionEvent::Unauthorized as u32).to_string(),
+        );
+
+    // the public key is stored in the form of the verify_signature advice inputs, Z = 1 is implied
+    let (public_key_x, public_key_y) = ecdsa_public_key(verifying_key);
+    let [x_high, x_low] = u256_to_words(&public_key_x);
+    let [y_high, y_low] = u256_to_words(&public_key_y);
+    let storage_slots = vec![
+        StorageSlot::Value(x_high),
+        StorageSlot::Value(x_low),
+        StorageSlot::Value(y_high),
+        StorageSlot::Value(y_low),
+    ];
+
+    AccountComponent::compile(auth_code, TransactionKernel::assembler(), storage_slots)
+        .unwrap()
+        .with_supports_all_types()
+}
+
+/// Returns a builder of a regular wallet controlled by `verifying_key`, to which the storage mode
+/// and further components can be added.
+pub fn ecdsa_wallet_builder(init_seed: [u8; 32], verifying_key: &VerifyingKey) -> AccountBuilder {
+    AccountBuilder::new(init_seed)
+        .account_type(AccountType::RegularAccountUpdatableCode)
+        .with_auth_component(ecdsa_auth_component(verifying_key))
+        .with_component(BasicWallet)
+}
+
+/// Returns the 32-byte message hash signed for a transaction summary commitment
+/// `[c0, c1, c2, c3]`: the big-endian encoding of `c0 + c1 * 2^64 + c2 * 2^128 + c3 * 2^192`.
+pub fn tx_summary_prehash(commitment: Word) -> [u8; 32] {
+    let mut prehash = [0u8; 32];
+    for (i, felt) in commitment.iter().enumerate() {
+        let end = 32 - 8 * i;
+        prehash[end - 8..end].copy_from_slice(&felt.as_int().to_be_bytes());
+    }
+    prehash
+}
+
+/// Returns the advice map entry authenticating the transaction of `summary` with `signature`,
+/// made by `verifying_key` over [`tx_summary_prehash`].
+pub fn tx_summary_signature_advice(
+    verifying_key: &VerifyingKey,
+    signature: &Signature,
+    summary: &TransactionSummary,
+) -> (Word, Vec<Felt>) {
+    let commitment = summary.to_commitment();
+    let inputs =
+        EcdsaAdviceInputs::from_k256(verifying_key, signature, &tx_summary_prehash(commitment));
+
+    let mut value = Vec::with_capacity(16);
+    for word in u256_to_words(&inputs.signature_s)
+        .into_iter()
+        .chain(u256_to_words(&inputs.signature_r))
+    {
+        value.extend(word.iter().copied());
+    }
+    (commitment, value)
+}
+
+/// Signs the transaction of `summary` with `signing_key` and returns the advice map entry
+/// authenticating it.
+pub fn sign_tx_summary(
+    signing_key: &SigningKey,
+    summary: &TransactionSummary,
+) -> (Word, Vec<Felt>) {
+    let prehash = tx_summary_prehash(summary.to_commitment());
+    let signature: Signature = signing_key.sign_prehash(&prehash).unwrap();
+    tx_summary_signature_advice(signing_key.verifying_key(), &signature, summary)
+}
+
+/// Executes `transaction_request` against the ECDSA authenticated `account_id` without
+/// signature, and returns the transaction summary to sign.
+pub async fn propose_ecdsa_transaction<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account_id: AccountId,
+    transaction_request: TransactionRequest,
+) -> Result<TransactionSummary, EcdsaAuthError> {
+    match client
+        .new_transaction(account_id, transaction_request)
+        .await
+    {
+        Ok(_) => Err(EcdsaAuthError::NotSignatureRequest),
+        Err(ClientError::TransactionExecutorError(TransactionExecutorError::Unauthorized(
+            summary,
+        ))) => Ok(*summary),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Executes `transaction_request` against the ECDSA authenticated `account_id`, signing its
+/// summary with `signing_key`.
+///
+/// The returned transaction still has to be submitted.
+pub async fn new_ecdsa_transaction<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    account_id: AccountId,
+    mut transaction_request: TransactionRequest,
+    signing_key: &SigningKey,
+) -> Result<TransactionResult, EcdsaAuthError> {
+    let summary =
+        propose_ecdsa_transaction(client, account_id, transaction_request.clone()).await?;
+    transaction_request
+        .advice_map_mut()
+        .extend(vec![sign_tx_summary(signing_key, &summary)]);
+
+    Ok(client
+        .new_transaction(account_id, transaction_request)
+        .await?)
+}
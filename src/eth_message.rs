@@ -0,0 +1,184 @@
+//! Messages signed by Ethereum wallets.
+//!
+//! Encoders of the EIP-191 `personal_sign` and EIP-712 typed-data hashes, matching
+//! `evm_contract::personal_sign_hash` and `evm_contract::eip712_action_hash`, which rebuild the
+//! signed digest on-chain.
+//!
+//! `evm_contract::verify_eth_action` verifies a [`MidenAction`] for the stored action nonce of the
+//! EVM account and increments it, so that each signed action is only accepted once.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use sha3::{Digest, Keccak256};
+use thiserror::Error;
+
+use miden_client::account::AccountId;
+use miden_client::keystore::FilesystemKeyStore;
+use miden_client::{Client as MidenClient, ClientError, Felt, Word};
+use miden_objects::vm::AdviceMap;
+use rand::rngs::StdRng;
+
+use crate::common::prepare_felt_vec;
+use crate::constants::{ETH_ACTION_MESSAGE_KEY_SLOT, ETH_ACTION_NONCE_SLOT};
+use crate::ecdsa::{EcdsaAdviceInputs, EcdsaError};
+use crate::ecdsa_auth::tx_summary_prehash;
+
+type Client = MidenClient<FilesystemKeyStore<StdRng>>;
+
+/// Represents errors that can occur while reading the action state of EVM accounts.
+#[derive(Debug, Error)]
+pub enum EthMessageError {
+    #[error("account {0} not found in the client store")]
+    /// The account is not tracked by the client.
+    AccountNotFound(AccountId),
+    #[error("account {0} has no action nonce slot")]
+    /// The account storage has no action nonce, it is not an EVM account.
+    NotAnEvmAccount(AccountId),
+    #[error("client error: {0}")]
+    /// The client store could not be read.
+    Client(#[from] ClientError),
+}
+
+/// Type of the Miden action struct signed with EIP-712.
+pub const MIDEN_ACTION_TYPE: &str = "MidenAction(uint128 accountId,uint64 nonce,bytes32 txSummary)";
+
+/// Returns the EIP-191 `personal_sign` hash of `message`:
+/// `keccak256("\x19Ethereum Signed Message:\n" || len(message) || message)`.
+pub fn personal_sign_hash(message: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(b"\x19Ethereum Signed Message:\n");
+    hasher.update(message.len().to_string().as_bytes());
+    hasher.update(message);
+    hasher.finalize().into()
+}
+
+/// Returns the EIP-712 hash of a struct: `keccak256("\x19\x01" || domain_separator ||
+/// struct_hash)`.
+pub fn eip712_hash(domain_separator: &[u8; 32], struct_hash: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(b"\x19\x01");
+    hasher.update(domain_separator);
+    hasher.update(struct_hash);
+    hasher.finalize().into()
+}
+
+/// An EIP-712 domain, with the optional fields left out of its type when absent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Eip712Domain {
+    pub name: String,
+    pub version: String,
+    pub chain_id: Option<u64>,
+    pub verifying_contract: Option<[u8; 20]>,
+}
+
+impl Eip712Domain {
+    /// Returns the domain of Miden actions, `{name: "Miden", version: "1"}`, whose separator is
+    /// hardcoded in evm.masm.
+    pub fn miden() -> Self {
+        Self {
+            name: "Miden".into(),
+            version: "1".into(),
+            chain_id: None,
+            verifying_contract: None,
+        }
+    }
+
+    /// Returns the domain separator, the hash of the domain struct.
+    pub fn separator(&self) -> [u8; 32] {
+        let mut domain_type = String::from("EIP712Domain(string name,string version");
+        let mut encoded: Vec<u8> = Vec::with_capacity(5 * 32);
+        encoded.extend(keccak256(self.name.as_bytes()));
+        encoded.extend(keccak256(self.version.as_bytes()));
+        if let Some(chain_id) = self.chain_id {
+            domain_type.push_str(",uint256 chainId");
+            encoded.extend(encode_uint(chain_id as u128));
+        }
+        if let Some(verifying_contract) = self.verifying_contract {
+            domain_type.push_str(",address verifyingContract");
+            encoded.extend([0u8; 12]);
+            encoded.extend(verifying_contract);
+        }
+        domain_type.push(')');
+
+        let mut hasher = Keccak256::new();
+        hasher.update(keccak256(domain_type.as_bytes()));
+        hasher.update(encoded);
+        hasher.finalize().into()
+    }
+}
+
+/// A transaction of a Miden account approved from an Ethereum wallet with EIP-712.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MidenAction {
+    pub account_id: AccountId,
+    pub nonce: Felt,
+    /// Commitment to the transaction summary, or to the operation authorized with
+    /// `evm_contract::verify_eth_action`.
+    pub tx_summary: Word,
+}
+
+impl MidenAction {
+    /// Returns the hash of the action struct, with the account id encoded as
+    /// `prefix * 2^64 + suffix` and the transaction summary as in [`tx_summary_prehash`].
+    pub fn struct_hash(&self) -> [u8; 32] {
+        let account_id = ((self.account_id.prefix().as_u64() as u128) << 64)
+            | self.account_id.suffix().as_int() as u128;
+
+        let mut hasher = Keccak256::new();
+        hasher.update(keccak256(MIDEN_ACTION_TYPE.as_bytes()));
+        hasher.update(encode_uint(account_id));
+        hasher.update(encode_uint(self.nonce.as_int() as u128));
+        hasher.update(tx_summary_prehash(self.tx_summary));
+        hasher.finalize().into()
+    }
+
+    /// Returns the EIP-712 hash of the action in the Miden domain, as signed by the wallet.
+    pub fn eip712_hash(&self) -> [u8; 32] {
+        eip712_hash(&Eip712Domain::miden().separator(), &self.struct_hash())
+    }
+}
+
+/// Returns the advice map read by `evm_contract::verify_eth_action` for `action`, authorized by
+/// the 65-byte Ethereum signature `r || s || v` of its EIP-712 hash.
+pub fn eth_action_advice(
+    action: &MidenAction,
+    signature: &[u8; 65],
+) -> Result<AdviceMap, EcdsaError> {
+    let inputs = EcdsaAdviceInputs::from_eth_signature(signature, &action.eip712_hash())?;
+    let mut advice_map = inputs.to_advice_map();
+    advice_map.insert(
+        prepare_felt_vec(ETH_ACTION_MESSAGE_KEY_SLOT).into(),
+        action.tx_summary.to_vec(),
+    );
+    Ok(advice_map)
+}
+
+/// Reads the action nonce of the EVM account `account_id` from the client store: the nonce the
+/// next action signed for the account must commit to.
+pub async fn get_eth_action_nonce(
+    client: &Client,
+    account_id: AccountId,
+) -> Result<Felt, EthMessageError> {
+    let account = client
+        .get_account(account_id)
+        .await?
+        .ok_or(EthMessageError::AccountNotFound(account_id))?;
+    let nonce = account
+        .account()
+        .storage()
+        .get_item(ETH_ACTION_NONCE_SLOT)
+        .map_err(|_| EthMessageError::NotAnEvmAccount(account_id))?;
+
+    Ok(nonce[0])
+}
+
+fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    Keccak256::digest(bytes).into()
+}
+
+// uint values are encoded as 32 big-endian bytes
+fn encode_uint(value: u128) -> [u8; 32] {
+    let mut encoded = [0u8; 32];
+    encoded[16..].copy_from_slice(&value.to_be_bytes());
+    encoded
+}
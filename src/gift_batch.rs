@@ -0,0 +1,153 @@
+//! Batch creation of gift notes for promotions.
+//!
+//! All gifts of a batch are created by a single `own_output_notes` transaction from one funded
+//! account. Every gift gets its own generated secret; the note ids, secrets and claim links are
+//! exported as CSV so they can be distributed.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+use miden_client::account::AccountId;
+use miden_client::asset::{Asset, FungibleAsset};
+use miden_client::auth::TransactionAuthenticator;
+use miden_client::note::{Note, NoteAssets, NoteType};
+use miden_client::transaction::{OutputNote, TransactionRequestBuilder, TransactionRequestError};
+use miden_client::{Client, ClientError, Word};
+use miden_objects::constants::MAX_OUTPUT_NOTES_PER_TX;
+use miden_objects::{AssetError, NoteError};
+
+use crate::common::{create_gift_note_with_script, gift_note_script};
+
+/// Header row of the CSV written by [`write_gift_batch_csv`].
+pub const GIFT_BATCH_CSV_HEADER: &str = "note_id,faucet_id,amount,secret,link";
+
+/// Represents errors that can occur while creating a gift batch.
+#[derive(Debug, Error)]
+pub enum GiftBatchError {
+    #[error("gift batch is empty")]
+    /// No gift amounts were provided.
+    Empty,
+    #[error("gift batch of {0} notes exceeds the {MAX_OUTPUT_NOTES_PER_TX} output notes per tx")]
+    /// The batch does not fit into a single transaction.
+    TooLarge(usize),
+    #[error("invalid gift amount: {0}")]
+    /// A gift amount does not form a valid fungible asset.
+    Asset(#[from] AssetError),
+    #[error("failed to build gift note: {0}")]
+    /// A gift note could not be built.
+    Note(#[from] NoteError),
+    #[error("failed to build gift batch request: {0}")]
+    /// The batch transaction request could not be built.
+    Request(#[from] TransactionRequestError),
+    #[error("failed to submit gift batch: {0}")]
+    /// The batch transaction failed.
+    Transaction(#[from] ClientError),
+}
+
+/// A gift created as part of a batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GiftBatchEntry {
+    pub note: Note,
+    pub faucet_id: AccountId,
+    pub amount: u64,
+    pub secret: Word,
+}
+
+impl GiftBatchEntry {
+    /// Returns the claim link of the gift: `{link_base}?note={note_id}&secret={secret}`.
+    pub fn link(&self, link_base: &str) -> String {
+        format!(
+            "{}?note={}&secret={}",
+            link_base,
+            self.note.id().to_hex(),
+            self.secret.to_hex()
+        )
+    }
+}
+
+/// Creates one gift note per entry of `amounts`, all funded by `creator` with tokens of
+/// `faucet_id`, and submits them in a single transaction.
+pub async fn create_gift_batch<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    creator: AccountId,
+    faucet_id: AccountId,
+    amounts: &[u64],
+) -> Result<Vec<GiftBatchEntry>, GiftBatchError> {
+    if amounts.is_empty() {
+        return Err(GiftBatchError::Empty);
+    }
+    if amounts.len() > MAX_OUTPUT_NOTES_PER_TX {
+        return Err(GiftBatchError::TooLarge(amounts.len()));
+    }
+
+    // the script is compiled once and shared by every gift of the batch
+    let note_script = gift_note_script();
+
+    let mut entries = Vec::with_capacity(amounts.len());
+    for &amount in amounts {
+        let rng = client.rng();
+        let serial_num = rng.inner_mut().draw_word();
+        let secret = rng.inner_mut().draw_word();
+
+        let asset = FungibleAsset::new(faucet_id, amount)?;
+        let note = create_gift_note_with_script(
+            creator,
+            NoteAssets::new(vec![Asset::Fungible(asset)])?,
+            secret.into(),
+            serial_num.into(),
+            NoteType::Public,
+            None,
+            note_script.clone(),
+        )?;
+
+        entries.push(GiftBatchEntry {
+            note,
+            faucet_id,
+            amount,
+            secret,
+        });
+    }
+
+    let tx_request = TransactionRequestBuilder::new()
+        .own_output_notes(
+            entries
+                .iter()
+                .map(|entry| OutputNote::Full(entry.note.clone()))
+                .collect(),
+        )
+        .build()?;
+
+    let tx_result = client.new_transaction(creator, tx_request).await?;
+    client.submit_transaction(tx_result).await?;
+
+    Ok(entries)
+}
+
+/// Renders the batch as CSV, one row per gift.
+pub fn gift_batch_csv(entries: &[GiftBatchEntry], link_base: &str) -> String {
+    let mut csv = String::from(GIFT_BATCH_CSV_HEADER);
+    csv.push('\n');
+    for entry in entries {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            entry.note.id().to_hex(),
+            entry.faucet_id.to_hex(),
+            entry.amount,
+            entry.secret.to_hex(),
+            entry.link(link_base)
+        ));
+    }
+    csv
+}
+
+/// Writes the batch as CSV to `path`.
+pub fn write_gift_batch_csv(
+    path: &Path,
+    entries: &[GiftBatchEntry],
+    link_base: &str,
+) -> std::io::Result<()> {
+    fs::write(path, gift_batch_csv(entries, link_base))
+}
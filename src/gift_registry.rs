@@ -0,0 +1,316 @@
+//! Tracking of the gifts created by our accounts.
+//!
+//! The registry keeps one [`GiftRecord`] per gift note in the miden-client store settings, and
+//! resolves the [`GiftStatus`] of every gift from the note state the client learns while syncing:
+//! inclusion of the gift note and the nullifier emitted once it is consumed.
+//!
+//! A gift with an expiry can only be opened with its secret before that block; from then on only
+//! its creator can reclaim it (see [`crate::common::create_expiring_gift_note`]). The nullifier
+//! does not tell who consumed a gift, so a gift is reported as reclaimed when it was consumed by a
+//! transaction of its creator made with this client.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use thiserror::Error;
+
+use miden_client::account::AccountId;
+use miden_client::asset::Asset;
+use miden_client::auth::TransactionAuthenticator;
+use miden_client::block::BlockNumber;
+use miden_client::note::{Note, NoteId};
+use miden_client::store::{InputNoteState, TransactionFilter};
+use miden_client::utils::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+use miden_client::{Client, ClientError, Word};
+
+/// Key under which the registry is persisted in the client store settings.
+pub const GIFT_REGISTRY_SETTING: &str = "gift_registry";
+
+/// Represents errors that can occur in the gift registry.
+#[derive(Debug, Error)]
+pub enum GiftRegistryError {
+    #[error("gift {0} is already registered")]
+    /// The gift note was registered before.
+    AlreadyRegistered(NoteId),
+    #[error("note {0} is not a gift note")]
+    /// The note does not carry a secret commitment.
+    NotAGift(NoteId),
+    #[error("gift registry store error: {0}")]
+    /// The client store could not be read or updated.
+    Store(#[from] ClientError),
+}
+
+/// Claim status of a gift.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GiftStatus {
+    /// The gift has not been opened yet.
+    Pending,
+    /// The gift was opened with its secret by someone other than its creator.
+    Claimed,
+    /// The gift went back to its creator.
+    Reclaimed,
+    /// The gift was not opened before its expiry block, only its creator can reclaim it.
+    Expired,
+}
+
+impl fmt::Display for GiftStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            GiftStatus::Pending => "pending",
+            GiftStatus::Claimed => "claimed",
+            GiftStatus::Reclaimed => "reclaimed",
+            GiftStatus::Expired => "expired",
+        };
+        f.write_str(status)
+    }
+}
+
+/// A gift created by one of our accounts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GiftRecord {
+    pub note_id: NoteId,
+    pub creator: AccountId,
+    pub assets: Vec<Asset>,
+    pub expiry: Option<BlockNumber>,
+    pub secret_commitment: Word,
+}
+
+impl GiftRecord {
+    /// Builds the record of a gift note created by [`crate::common::create_gift_note`] or
+    /// [`crate::common::create_expiring_gift_note`].
+    pub fn from_note(note: &Note) -> Result<Self, GiftRegistryError> {
+        // [SECRET_COMMITMENT, reclaim_height], a reclaim height of 0 meaning no expiry
+        let inputs: [_; 5] = note
+            .recipient()
+            .inputs()
+            .values()
+            .try_into()
+            .map_err(|_| GiftRegistryError::NotAGift(note.id()))?;
+        let reclaim_height = u32::try_from(inputs[4].as_int())
+            .map_err(|_| GiftRegistryError::NotAGift(note.id()))?;
+        let expiry = (reclaim_height != 0).then(|| BlockNumber::from(reclaim_height));
+
+        Ok(Self {
+            note_id: note.id(),
+            creator: note.metadata().sender(),
+            assets: note.assets().iter().copied().collect(),
+            expiry,
+            secret_commitment: Word::from([inputs[0], inputs[1], inputs[2], inputs[3]]),
+        })
+    }
+}
+
+impl Serializable for GiftRecord {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.note_id.write_into(target);
+        self.creator.write_into(target);
+        self.assets.write_into(target);
+        self.expiry.write_into(target);
+        self.secret_commitment.write_into(target);
+    }
+}
+
+impl Deserializable for GiftRecord {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            note_id: NoteId::read_from(source)?,
+            creator: AccountId::read_from(source)?,
+            assets: Vec::<Asset>::read_from(source)?,
+            expiry: Option::<BlockNumber>::read_from(source)?,
+            secret_commitment: Word::read_from(source)?,
+        })
+    }
+}
+
+/// A gift record together with its resolved status.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GiftState {
+    pub record: GiftRecord,
+    pub status: GiftStatus,
+    /// Whether the gift note was included in a block.
+    pub committed: bool,
+}
+
+/// Registry of the gifts created by our accounts, persisted in the client store.
+#[derive(Debug, Clone, Default)]
+pub struct GiftRegistry {
+    records: Vec<GiftRecord>,
+}
+
+impl GiftRegistry {
+    /// Loads the registry from the client store, starting empty if nothing was recorded yet.
+    pub async fn load<AUTH: TransactionAuthenticator + Sync + 'static>(
+        client: &Client<AUTH>,
+    ) -> Result<Self, GiftRegistryError> {
+        let records = client
+            .get_setting::<Vec<GiftRecord>>(GIFT_REGISTRY_SETTING.to_string())
+            .await?
+            .unwrap_or_default();
+
+        Ok(Self { records })
+    }
+
+    /// Returns the recorded gifts.
+    pub fn records(&self) -> &[GiftRecord] {
+        &self.records
+    }
+
+    /// Records a gift note and persists the registry.
+    pub async fn record<AUTH: TransactionAuthenticator + Sync + 'static>(
+        &mut self,
+        client: &mut Client<AUTH>,
+        note: &Note,
+    ) -> Result<(), GiftRegistryError> {
+        if self
+            .records
+            .iter()
+            .any(|record| record.note_id == note.id())
+        {
+            return Err(GiftRegistryError::AlreadyRegistered(note.id()));
+        }
+
+        self.records.push(GiftRecord::from_note(note)?);
+        client
+            .set_setting(GIFT_REGISTRY_SETTING.to_string(), self.records.clone())
+            .await?;
+        Ok(())
+    }
+
+    /// Syncs the client and resolves the status of every recorded gift.
+    pub async fn resolve<AUTH: TransactionAuthenticator + Sync + 'static>(
+        &self,
+        client: &mut Client<AUTH>,
+    ) -> Result<Vec<GiftState>, GiftRegistryError> {
+        client.sync_state().await?;
+        let sync_height = client.get_sync_height().await?;
+
+        let mut states = Vec::with_capacity(self.records.len());
+        for record in &self.records {
+            let output_note = client.get_output_note(record.note_id).await?;
+            let consumed = output_note.as_ref().is_some_and(|note| note.is_consumed());
+            // a consumed note was necessarily included in a block first
+            let committed =
+                consumed || output_note.as_ref().is_some_and(|note| note.is_committed());
+
+            let status = if consumed {
+                if Self::consumed_by(client, record).await? == Some(record.creator) {
+                    GiftStatus::Reclaimed
+                } else {
+                    GiftStatus::Claimed
+                }
+            } else if record.expiry.is_some_and(|expiry| sync_height >= expiry) {
+                GiftStatus::Expired
+            } else {
+                GiftStatus::Pending
+            };
+
+            states.push(GiftState {
+                record: record.clone(),
+                status,
+                committed,
+            });
+        }
+
+        Ok(states)
+    }
+
+    /// Syncs the client and summarizes the recorded gifts.
+    pub async fn summary<AUTH: TransactionAuthenticator + Sync + 'static>(
+        &self,
+        client: &mut Client<AUTH>,
+    ) -> Result<GiftSummary, GiftRegistryError> {
+        Ok(GiftSummary {
+            gifts: self.resolve(client).await?,
+        })
+    }
+
+    /// Returns the local account that consumed the gift note, if the consumption happened in
+    /// this client.
+    async fn consumed_by<AUTH: TransactionAuthenticator + Sync + 'static>(
+        client: &Client<AUTH>,
+        record: &GiftRecord,
+    ) -> Result<Option<AccountId>, ClientError> {
+        let Some(input_note) = client.get_input_note(record.note_id).await? else {
+            return Ok(None);
+        };
+        if matches!(input_note.state(), InputNoteState::ConsumedExternal(_)) {
+            return Ok(None);
+        }
+        let Some(tx_id) = input_note.consumer_transaction_id() else {
+            return Ok(None);
+        };
+
+        let transactions = client
+            .get_transactions(TransactionFilter::Ids(vec![*tx_id]))
+            .await?;
+        Ok(transactions.first().map(|tx| tx.details.account_id))
+    }
+}
+
+/// CLI-friendly overview of the recorded gifts.
+#[derive(Debug, Clone)]
+pub struct GiftSummary {
+    pub gifts: Vec<GiftState>,
+}
+
+impl GiftSummary {
+    /// Returns the number of gifts with the given status.
+    pub fn count(&self, status: GiftStatus) -> usize {
+        self.gifts
+            .iter()
+            .filter(|gift| gift.status == status)
+            .count()
+    }
+}
+
+impl fmt::Display for GiftSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<66}  {:<10}  {:<9}  {:<8}  assets",
+            "note id", "status", "committed", "expiry"
+        )?;
+        for gift in &self.gifts {
+            let expiry = gift
+                .record
+                .expiry
+                .map(|expiry| expiry.to_string())
+                .unwrap_or_else(|| String::from("-"));
+            let assets = gift
+                .record
+                .assets
+                .iter()
+                .map(|asset| match asset {
+                    Asset::Fungible(asset) => {
+                        format!("{} x {}", asset.amount(), asset.faucet_id().to_hex())
+                    }
+                    Asset::NonFungible(asset) => {
+                        format!("nft {}", asset.faucet_id_prefix().to_hex())
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            writeln!(
+                f,
+                "{:<66}  {:<10}  {:<9}  {:<8}  {}",
+                gift.record.note_id.to_hex(),
+                gift.status.to_string(),
+                gift.committed,
+                expiry,
+                assets
+            )?;
+        }
+        write!(
+            f,
+            "{} gifts: {} pending, {} claimed, {} reclaimed, {} expired",
+            self.gifts.len(),
+            self.count(GiftStatus::Pending),
+            self.count(GiftStatus::Claimed),
+            self.count(GiftStatus::Reclaimed),
+            self.count(GiftStatus::Expired)
+        )
+    }
+}
@@ -0,0 +1,277 @@
+//! Hash time-locked contract notes for cross-chain atomic swaps.
+//!
+//! An HTLC note locks assets behind the SHA-256 digest of a secret preimage, the same lock used
+//! by Bitcoin and EVM HTLCs. The designated recipient claims the assets by revealing the preimage
+//! before the timeout block; from the timeout on, the sender can refund them. Claiming publishes a
+//! reveal note carrying the preimage, from which the counterparty learns the secret it needs to
+//! claim on the other chain.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+use thiserror::Error;
+
+use miden_client::account::AccountId;
+use miden_client::auth::TransactionAuthenticator;
+use miden_client::block::BlockNumber;
+use miden_client::note::{
+    Note, NoteAssets, NoteExecutionHint, NoteInputs, NoteMetadata, NoteRecipient, NoteScript,
+    NoteTag, NoteType,
+};
+use miden_client::store::NoteFilter;
+use miden_client::transaction::{TransactionRequest, TransactionRequestBuilder};
+use miden_client::{Client, ClientError, Felt, Word};
+use miden_objects::NoteError;
+
+use crate::codec::{
+    be_felts_to_bytes, bytes_to_be_felts, length_prefixed_be_felts,
+    length_prefixed_be_felts_to_bytes,
+};
+use crate::common::{compile_note_script_with_assets, sha256_preimage_advice, word_to_masm};
+
+/// Number of note inputs of an HTLC note.
+pub const HTLC_NUM_INPUTS: usize = 12;
+
+/// Represents errors that can occur while building, claiming or reading HTLC notes.
+#[derive(Debug, Error)]
+pub enum HtlcError {
+    #[error("note is not an HTLC note")]
+    /// The note does not run the HTLC script or has malformed inputs.
+    NotAnHtlc,
+    #[error("note is not an HTLC reveal note")]
+    /// The note does not carry a length-prefixed preimage.
+    NotARevealNote,
+    #[error("preimage does not match the HTLC digest")]
+    /// The SHA-256 digest of the preimage differs from the hash lock.
+    PreimageMismatch,
+    #[error("failed to build HTLC note: {0}")]
+    /// A note could not be built.
+    Note(#[from] NoteError),
+    #[error("HTLC client error: {0}")]
+    /// The client store could not be read.
+    Client(#[from] ClientError),
+}
+
+/// An HTLC note together with the parameters encoded in its inputs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtlcNote {
+    note: Note,
+    recipient: AccountId,
+    timeout: BlockNumber,
+    digest: [u8; 32],
+}
+
+impl HtlcNote {
+    /// Builds an HTLC note from `sender` paying `assets` to `recipient` against the preimage of
+    /// the SHA-256 `digest`, refundable to `sender` from block `timeout` on.
+    pub fn new(
+        sender: AccountId,
+        recipient: AccountId,
+        assets: NoteAssets,
+        digest: [u8; 32],
+        timeout: BlockNumber,
+        serial_num: Word,
+    ) -> Result<Self, HtlcError> {
+        let reveal_tag = NoteTag::from_account_id(sender);
+
+        let mut inputs = bytes_to_be_felts(&digest);
+        inputs.extend([
+            recipient.suffix(),
+            recipient.prefix().as_felt(),
+            Felt::from(timeout.as_u32()),
+            Felt::from(reveal_tag),
+        ]);
+
+        // the recipient watches for notes tagged with its account
+        let metadata = NoteMetadata::new(
+            sender,
+            NoteType::Public,
+            NoteTag::from_account_id(recipient),
+            NoteExecutionHint::always(),
+            Felt::new(0),
+        )?;
+        let note_recipient =
+            NoteRecipient::new(serial_num, htlc_script(), NoteInputs::new(inputs)?);
+        let note = Note::new(assets, metadata, note_recipient);
+
+        Ok(Self {
+            note,
+            recipient,
+            timeout,
+            digest,
+        })
+    }
+
+    /// Builds an HTLC note locked with the SHA-256 digest of `preimage`.
+    pub fn from_preimage(
+        sender: AccountId,
+        recipient: AccountId,
+        assets: NoteAssets,
+        preimage: &[u8],
+        timeout: BlockNumber,
+        serial_num: Word,
+    ) -> Result<Self, HtlcError> {
+        Self::new(
+            sender,
+            recipient,
+            assets,
+            Sha256::digest(preimage).into(),
+            timeout,
+            serial_num,
+        )
+    }
+
+    /// Reads the HTLC parameters back from an HTLC note, e.g. one received from the sender.
+    pub fn from_note(note: Note) -> Result<Self, HtlcError> {
+        if note.script().root() != htlc_script().root() {
+            return Err(HtlcError::NotAnHtlc);
+        }
+        let inputs = note.recipient().inputs().values();
+        if inputs.len() != HTLC_NUM_INPUTS {
+            return Err(HtlcError::NotAnHtlc);
+        }
+
+        let digest: [u8; 32] = be_felts_to_bytes(&inputs[..8], 32)
+            .map_err(|_| HtlcError::NotAnHtlc)?
+            .try_into()
+            .unwrap();
+        let recipient =
+            AccountId::try_from([inputs[9], inputs[8]]).map_err(|_| HtlcError::NotAnHtlc)?;
+        let timeout = u32::try_from(inputs[10].as_int()).map_err(|_| HtlcError::NotAnHtlc)?;
+
+        Ok(Self {
+            note,
+            recipient,
+            timeout: timeout.into(),
+            digest,
+        })
+    }
+
+    /// Returns the HTLC note.
+    pub fn note(&self) -> &Note {
+        &self.note
+    }
+
+    /// Returns the account that funded the HTLC and can refund it.
+    pub fn sender(&self) -> AccountId {
+        self.note.metadata().sender()
+    }
+
+    /// Returns the account that can claim the HTLC.
+    pub fn recipient(&self) -> AccountId {
+        self.recipient
+    }
+
+    /// Returns the block from which the HTLC can no longer be claimed but refunded.
+    pub fn timeout(&self) -> BlockNumber {
+        self.timeout
+    }
+
+    /// Returns the SHA-256 digest locking the HTLC.
+    pub fn digest(&self) -> [u8; 32] {
+        self.digest
+    }
+
+    /// Returns the recipient of the reveal note created when the HTLC is claimed with
+    /// `preimage`.
+    pub fn reveal_recipient(&self, preimage: &[u8]) -> Result<NoteRecipient, HtlcError> {
+        Ok(NoteRecipient::new(
+            self.reveal_serial_num(),
+            htlc_reveal_script(),
+            NoteInputs::new(length_prefixed_be_felts(preimage))?,
+        ))
+    }
+
+    /// Builds the request claiming the HTLC for its recipient by revealing `preimage`.
+    pub fn claim_request(&self, preimage: &[u8]) -> Result<TransactionRequest, HtlcError> {
+        let digest: [u8; 32] = Sha256::digest(preimage).into();
+        if digest != self.digest {
+            return Err(HtlcError::PreimageMismatch);
+        }
+
+        Ok(TransactionRequestBuilder::new()
+            .unauthenticated_input_notes([(self.note.clone(), None)])
+            .extend_advice_map(sha256_preimage_advice(preimage))
+            .expected_output_recipients(vec![self.reveal_recipient(preimage)?])
+            .build()
+            .unwrap())
+    }
+
+    /// Builds the request refunding the HTLC to its sender once the timeout is reached.
+    pub fn refund_request(&self) -> TransactionRequest {
+        TransactionRequestBuilder::new()
+            .unauthenticated_input_notes([(self.note.clone(), None)])
+            .build()
+            .unwrap()
+    }
+
+    /// Looks for the reveal note of this HTLC among the notes known to the client and returns
+    /// the revealed preimage.
+    ///
+    /// The reveal note is tagged for the sender, so the client of the sender picks it up while
+    /// syncing.
+    pub async fn find_revealed_preimage<AUTH: TransactionAuthenticator + Sync + 'static>(
+        &self,
+        client: &Client<AUTH>,
+    ) -> Result<Option<Vec<u8>>, HtlcError> {
+        let reveal_root = htlc_reveal_script().root();
+        let serial_num = self.reveal_serial_num();
+
+        for note in client.get_input_notes(NoteFilter::All).await? {
+            let details = note.details();
+            if details.script().root() != reveal_root || details.serial_num() != serial_num {
+                continue;
+            }
+            let preimage = extract_htlc_preimage(details.recipient())?;
+            if Sha256::digest(&preimage)[..] == self.digest[..] {
+                return Ok(Some(preimage));
+            }
+        }
+
+        Ok(None)
+    }
+
+    // The reveal note reuses the HTLC serial number with its last element incremented
+    fn reveal_serial_num(&self) -> Word {
+        let serial_num = self.note.serial_num();
+        Word::from([
+            serial_num[0],
+            serial_num[1],
+            serial_num[2],
+            serial_num[3] + Felt::new(1),
+        ])
+    }
+}
+
+/// Extracts the preimage revealed by an HTLC claim from the recipient of its reveal note.
+pub fn extract_htlc_preimage(reveal: &NoteRecipient) -> Result<Vec<u8>, HtlcError> {
+    if reveal.script().root() != htlc_reveal_script().root() {
+        return Err(HtlcError::NotARevealNote);
+    }
+    length_prefixed_be_felts_to_bytes(reveal.inputs().values())
+        .map_err(|_| HtlcError::NotARevealNote)
+}
+
+/// Compiles the HTLC note script, linking it to the reveal note script.
+pub fn htlc_script() -> NoteScript {
+    let note_code = read_note_code("htlc.masm").replace(
+        "{{HTLC_REVEAL_SCRIPT_ROOT}}",
+        &word_to_masm(htlc_reveal_script().root()),
+    );
+    compile_note_script_with_assets(note_code)
+}
+
+/// Compiles the HTLC reveal note script.
+pub fn htlc_reveal_script() -> NoteScript {
+    compile_note_script_with_assets(read_note_code("htlc_reveal.masm"))
+}
+
+fn read_note_code(file_name: &str) -> String {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let path: PathBuf = [manifest_dir, "masm", "notes", file_name].iter().collect();
+
+    fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("Error reading {}: {}", path.display(), err))
+}
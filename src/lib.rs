@@ -1,6 +1,19 @@
 #[macro_use]
 extern crate alloc;
 
+pub mod codec;
 pub mod common;
 pub mod constants;
+pub mod ecdsa;
+pub mod ecdsa_auth;
+pub mod eth_message;
+pub mod gift_batch;
+pub mod gift_registry;
+pub mod htlc;
+pub mod limit_order;
 pub mod multisig_client;
+pub mod private_gift;
+pub mod zoro_pool;
+pub mod zoro_quote;
+pub mod zoro_route;
+pub mod zoro_swap;
@@ -0,0 +1,313 @@
+//! Limit-order notes.
+//!
+//! A limit order offers a fungible asset at a fixed price in another fungible asset. A taker can
+//! fill any part of it: the taker receives the part of the offered asset bought by the fill, the
+//! maker is paid the fill amount in a P2ID note and the unfilled part goes into a remainder order
+//! on the same terms. The maker cancels the order by consuming it.
+
+use alloc::string::String;
+use std::fs;
+use std::path::PathBuf;
+use thiserror::Error;
+
+use miden_client::account::AccountId;
+use miden_client::asset::{Asset, FungibleAsset};
+use miden_client::note::{
+    Note, NoteAssets, NoteExecutionHint, NoteExecutionMode, NoteInputs, NoteMetadata,
+    NoteRecipient, NoteScript, NoteTag, NoteType,
+};
+use miden_client::transaction::{
+    TransactionRequest, TransactionRequestBuilder, TransactionRequestError,
+};
+use miden_client::{Felt, ScriptBuilder, Word, ZERO};
+use miden_lib::note::utils::build_p2id_recipient;
+use miden_lib::note::well_known_note::WellKnownNote;
+use miden_objects::NoteError;
+
+use crate::common::{wide_math_library, word_to_masm};
+
+/// Number of note inputs of a limit-order note.
+pub const LIMIT_ORDER_NUM_INPUTS: usize = 8;
+
+/// Represents errors that can occur while building, filling or reading limit-order notes.
+#[derive(Debug, Error)]
+pub enum LimitOrderError {
+    #[error("note is not a limit-order note")]
+    /// The note does not run the limit-order script or has malformed inputs.
+    NotALimitOrder,
+    #[error("order amounts have to be non-zero")]
+    /// The offered or requested amount is zero.
+    InvalidAmount,
+    #[error("fill of {0} tokens buys nothing or more than the order")]
+    /// The fill amount buys nothing or more than the offered asset.
+    InvalidFill(u64),
+    #[error("failed to build limit-order note: {0}")]
+    /// A note could not be built.
+    Note(#[from] NoteError),
+    #[error("failed to build limit-order request: {0}")]
+    /// A transaction request could not be built.
+    Request(#[from] TransactionRequestError),
+}
+
+/// A limit-order note together with the terms encoded in its inputs.
+///
+/// The price is stored as the ratio `price_requested / price_offered` reduced to lowest terms, so
+/// the remainder orders keep the price of the original order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LimitOrder {
+    note: Note,
+    maker: AccountId,
+    requested_faucet: AccountId,
+    price_requested: u64,
+    price_offered: u64,
+}
+
+impl LimitOrder {
+    /// Builds an order from `maker` selling `offered` for `requested`, i.e. at the price of
+    /// `requested.amount() / offered.amount()` tokens of the requested asset per offered token.
+    pub fn new(
+        maker: AccountId,
+        offered: FungibleAsset,
+        requested: FungibleAsset,
+        serial_num: Word,
+    ) -> Result<Self, LimitOrderError> {
+        if offered.amount() == 0 || requested.amount() == 0 {
+            return Err(LimitOrderError::InvalidAmount);
+        }
+        let divisor = gcd(requested.amount(), offered.amount());
+        let price_requested = requested.amount() / divisor;
+        let price_offered = offered.amount() / divisor;
+
+        let inputs = NoteInputs::new(vec![
+            // [price_requested, price_offered, requested_id_suffix, requested_id_prefix]
+            Felt::new(price_requested),
+            Felt::new(price_offered),
+            requested.faucet_id().suffix(),
+            requested.faucet_id().prefix().as_felt(),
+            // [maker_id_suffix, maker_id_prefix, p2id_tag, order_tag]
+            maker.suffix(),
+            maker.prefix().as_felt(),
+            Felt::from(NoteTag::from_account_id(maker)),
+            Felt::from(limit_order_tag()),
+        ])?;
+        let recipient = NoteRecipient::new(serial_num, limit_order_script(), inputs);
+        let note = Note::new(
+            NoteAssets::new(vec![Asset::Fungible(offered)])?,
+            order_metadata(maker)?,
+            recipient,
+        );
+
+        Ok(Self {
+            note,
+            maker,
+            requested_faucet: requested.faucet_id(),
+            price_requested,
+            price_offered,
+        })
+    }
+
+    /// Reads the order terms back from a limit-order note, e.g. a remainder order found by a
+    /// taker.
+    pub fn from_note(note: Note) -> Result<Self, LimitOrderError> {
+        if note.script().root() != limit_order_script().root() || note.assets().num_assets() != 1 {
+            return Err(LimitOrderError::NotALimitOrder);
+        }
+        if !matches!(note.assets().iter().next(), Some(Asset::Fungible(_))) {
+            return Err(LimitOrderError::NotALimitOrder);
+        }
+        let inputs = note.recipient().inputs().values();
+        if inputs.len() != LIMIT_ORDER_NUM_INPUTS {
+            return Err(LimitOrderError::NotALimitOrder);
+        }
+
+        let requested_faucet = AccountId::try_from([inputs[3], inputs[2]])
+            .map_err(|_| LimitOrderError::NotALimitOrder)?;
+        let maker = AccountId::try_from([inputs[5], inputs[4]])
+            .map_err(|_| LimitOrderError::NotALimitOrder)?;
+        if inputs[0] == ZERO || inputs[1] == ZERO {
+            return Err(LimitOrderError::NotALimitOrder);
+        }
+
+        Ok(Self {
+            price_requested: inputs[0].as_int(),
+            price_offered: inputs[1].as_int(),
+            note,
+            maker,
+            requested_faucet,
+        })
+    }
+
+    /// Returns the limit-order note.
+    pub fn note(&self) -> &Note {
+        &self.note
+    }
+
+    /// Returns the account that placed the order and is paid its fills.
+    pub fn maker(&self) -> AccountId {
+        self.maker
+    }
+
+    /// Returns the part of the offered asset still on sale.
+    pub fn offered_asset(&self) -> FungibleAsset {
+        self.note.assets().iter().next().unwrap().unwrap_fungible()
+    }
+
+    /// Returns the faucet of the requested asset.
+    pub fn requested_faucet(&self) -> AccountId {
+        self.requested_faucet
+    }
+
+    /// Returns the price as `(price_requested, price_offered)`: `price_requested` tokens of the
+    /// requested asset buy `price_offered` tokens of the offered asset.
+    pub fn price(&self) -> (u64, u64) {
+        (self.price_requested, self.price_offered)
+    }
+
+    /// Returns the amount of the offered asset bought by a fill of `fill_amount` tokens of the
+    /// requested asset, rounded down.
+    ///
+    /// The product is computed on 128 bits, as in the note script.
+    pub fn fill_amount_out(&self, fill_amount: u64) -> Result<u64, LimitOrderError> {
+        let amount_out =
+            fill_amount as u128 * self.price_offered as u128 / self.price_requested as u128;
+        if amount_out == 0 || amount_out > self.offered_asset().amount() as u128 {
+            return Err(LimitOrderError::InvalidFill(fill_amount));
+        }
+        Ok(amount_out as u64)
+    }
+
+    /// Returns the amount of the requested asset filling the whole order.
+    ///
+    /// Fills are rounded down in favor of the maker, so this is the smallest fill buying all of
+    /// the offered asset.
+    pub fn full_fill_amount(&self) -> u64 {
+        (self.offered_asset().amount() as u128 * self.price_requested as u128)
+            .div_ceil(self.price_offered as u128) as u64
+    }
+
+    /// Returns the recipient of the P2ID note paying a fill to the maker.
+    ///
+    /// The P2ID note uses the order serial number with its last element incremented.
+    pub fn maker_p2id_recipient(&self) -> Result<NoteRecipient, LimitOrderError> {
+        Ok(build_p2id_recipient(
+            self.maker,
+            offset_serial_num(self.note.serial_num(), 1),
+        )?)
+    }
+
+    /// Returns the remainder order created when `taker` fills `fill_amount` tokens, or `None` if
+    /// the fill buys the whole offered asset.
+    ///
+    /// The remainder order has the terms of this order, is sent by the taker and uses the order
+    /// serial number with 2 added to its last element.
+    pub fn remainder(
+        &self,
+        taker: AccountId,
+        fill_amount: u64,
+    ) -> Result<Option<LimitOrder>, LimitOrderError> {
+        let offered = self.offered_asset();
+        let remaining_amount = offered.amount() - self.fill_amount_out(fill_amount)?;
+        if remaining_amount == 0 {
+            return Ok(None);
+        }
+
+        let recipient = NoteRecipient::new(
+            offset_serial_num(self.note.serial_num(), 2),
+            self.note.script().clone(),
+            self.note.recipient().inputs().clone(),
+        );
+        let remaining_asset = FungibleAsset::new(offered.faucet_id(), remaining_amount)
+            .map_err(|_| LimitOrderError::InvalidFill(fill_amount))?;
+        let note = Note::new(
+            NoteAssets::new(vec![Asset::Fungible(remaining_asset)])?,
+            order_metadata(taker)?,
+            recipient,
+        );
+
+        Ok(Some(Self {
+            note,
+            ..self.clone()
+        }))
+    }
+
+    /// Builds the request filling `fill_amount` tokens of the order for `taker`.
+    ///
+    /// The taker account has to hold the fill amount of the requested asset.
+    pub fn fill_request(
+        &self,
+        taker: AccountId,
+        fill_amount: u64,
+    ) -> Result<TransactionRequest, LimitOrderError> {
+        let mut expected_recipients = vec![self.maker_p2id_recipient()?];
+        if let Some(remainder) = self.remainder(taker, fill_amount)? {
+            expected_recipients.push(remainder.note().recipient().clone());
+        }
+        let args = Word::from([Felt::new(fill_amount), ZERO, ZERO, ZERO]);
+
+        Ok(TransactionRequestBuilder::new()
+            .unauthenticated_input_notes([(self.note.clone(), Some(args))])
+            .expected_output_recipients(expected_recipients)
+            .build()?)
+    }
+
+    /// Builds the request cancelling the order, to be executed by the maker. The maker gets the
+    /// offered asset back.
+    pub fn cancel_request(&self) -> Result<TransactionRequest, LimitOrderError> {
+        Ok(TransactionRequestBuilder::new()
+            .unauthenticated_input_notes([(self.note.clone(), None)])
+            .build()?)
+    }
+}
+
+/// Returns the tag of limit-order notes, under which takers find them.
+pub fn limit_order_tag() -> NoteTag {
+    NoteTag::for_public_use_case(1, 0, NoteExecutionMode::Local).unwrap()
+}
+
+/// Compiles the limit-order note script, linking it to the P2ID note script of miden-lib and to
+/// wide_math.masm.
+pub fn limit_order_script() -> NoteScript {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let path: PathBuf = [manifest_dir, "masm", "notes", "limit_order.masm"]
+        .iter()
+        .collect();
+
+    let note_code: String = fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("Error reading {}: {}", path.display(), err))
+        // the maker is paid in standard P2ID notes
+        .replace(
+            "{{P2ID_SCRIPT_ROOT}}",
+            &word_to_masm(WellKnownNote::P2ID.script_root()),
+        );
+
+    // fills are priced on 128 bits, so any amounts can be traded
+    ScriptBuilder::new(true)
+        .with_statically_linked_library(&wide_math_library())
+        .unwrap()
+        .compile_note_script(note_code)
+        .unwrap()
+}
+
+// Orders and remainder orders are public and share the limit-order tag
+fn order_metadata(sender: AccountId) -> Result<NoteMetadata, NoteError> {
+    NoteMetadata::new(
+        sender,
+        NoteType::Public,
+        limit_order_tag(),
+        NoteExecutionHint::always(),
+        Felt::new(0),
+    )
+}
+
+fn offset_serial_num(serial_num: Word, offset: u64) -> Word {
+    Word::from([
+        serial_num[0],
+        serial_num[1],
+        serial_num[2],
+        serial_num[3] + Felt::new(offset),
+    ])
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
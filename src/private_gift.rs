@@ -0,0 +1,193 @@
+//! Off-chain delivery of private gift notes.
+//!
+//! A private gift only puts its note commitment on-chain. The full note and the secret needed to
+//! open it are sealed into an encrypted blob which is handed to the recipient (as a file, a link,
+//! a QR code, ...). The recipient opens the blob, imports the note into their client store and
+//! claims it once the note is committed.
+
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+use miden_client::auth::TransactionAuthenticator;
+use miden_client::block::BlockNumber;
+use miden_client::note::{Note, NoteDetails, NoteFile, NoteId, NoteType};
+use miden_client::utils::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+use miden_client::{Client, ClientError, Word};
+
+/// Length in bytes of the symmetric key sealing a [`PrivateGift`].
+pub const GIFT_KEY_LEN: usize = 32;
+
+/// Length in bytes of the XChaCha20 nonce prepended to a sealed gift.
+const NONCE_LEN: usize = 24;
+
+/// Represents errors that can occur while sealing or opening a private gift.
+#[derive(Debug, Error)]
+pub enum PrivateGiftError {
+    #[error("gift note is not private")]
+    /// The gift note was created as a public note.
+    NotPrivate,
+    #[error("failed to decrypt gift: wrong key or corrupted blob")]
+    /// The blob could not be authenticated with the provided key.
+    Decryption,
+    #[error("failed to decode gift: {0}")]
+    /// The decrypted payload is not a valid gift.
+    Decoding(String),
+    #[error("gift file error: {0}")]
+    /// The gift file could not be read or written.
+    Io(#[from] std::io::Error),
+    #[error("failed to import gift note: {0}")]
+    /// The client store rejected the gift note.
+    Import(#[from] ClientError),
+}
+
+/// A private gift note together with everything the recipient needs to claim it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrivateGift {
+    note: Note,
+    secret: Word,
+    after_block_num: BlockNumber,
+}
+
+impl PrivateGift {
+    /// Wraps a private gift note created with [`crate::common::create_gift_note`].
+    ///
+    /// `after_block_num` is the chain tip at the time the gift was submitted; the recipient's
+    /// client only looks for the note commitment from that block on.
+    pub fn new(
+        note: Note,
+        secret: Word,
+        after_block_num: BlockNumber,
+    ) -> Result<Self, PrivateGiftError> {
+        if note.metadata().note_type() != NoteType::Private {
+            return Err(PrivateGiftError::NotPrivate);
+        }
+
+        Ok(Self {
+            note,
+            secret,
+            after_block_num,
+        })
+    }
+
+    /// Returns the gift note.
+    pub fn note(&self) -> &Note {
+        &self.note
+    }
+
+    /// Returns the secret opening the gift note.
+    pub fn secret(&self) -> Word {
+        self.secret
+    }
+
+    /// Returns the block from which the recipient should look for the note commitment.
+    pub fn after_block_num(&self) -> BlockNumber {
+        self.after_block_num
+    }
+
+    /// Generates a fresh random key for [`PrivateGift::seal`].
+    pub fn generate_key<R: RngCore>(rng: &mut R) -> [u8; GIFT_KEY_LEN] {
+        let mut key = [0u8; GIFT_KEY_LEN];
+        rng.fill_bytes(&mut key);
+        key
+    }
+
+    /// Encrypts the gift with `key`.
+    ///
+    /// The returned blob is laid out as `nonce || ciphertext`, and can only be opened with the
+    /// same key.
+    pub fn seal<R: RngCore>(&self, key: &[u8; GIFT_KEY_LEN], rng: &mut R) -> Vec<u8> {
+        let mut nonce = [0u8; NONCE_LEN];
+        rng.fill_bytes(&mut nonce);
+
+        let cipher = XChaCha20Poly1305::new(key.into());
+        let ciphertext = cipher
+            .encrypt(XNonce::from_slice(&nonce), self.to_bytes().as_slice())
+            .expect("encrypting an in-memory buffer cannot fail");
+
+        let mut blob = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        blob.extend_from_slice(&nonce);
+        blob.extend_from_slice(&ciphertext);
+        blob
+    }
+
+    /// Decrypts a blob produced by [`PrivateGift::seal`].
+    pub fn open(blob: &[u8], key: &[u8; GIFT_KEY_LEN]) -> Result<Self, PrivateGiftError> {
+        if blob.len() < NONCE_LEN {
+            return Err(PrivateGiftError::Decryption);
+        }
+        let (nonce, ciphertext) = blob.split_at(NONCE_LEN);
+
+        let cipher = XChaCha20Poly1305::new(key.into());
+        let plaintext = cipher
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .map_err(|_| PrivateGiftError::Decryption)?;
+
+        let gift = Self::read_from_bytes(&plaintext)
+            .map_err(|e| PrivateGiftError::Decoding(e.to_string()))?;
+        Self::new(gift.note, gift.secret, gift.after_block_num)
+    }
+
+    /// Seals the gift with `key` and writes the blob to `path`.
+    pub fn write_sealed<R: RngCore>(
+        &self,
+        path: &Path,
+        key: &[u8; GIFT_KEY_LEN],
+        rng: &mut R,
+    ) -> Result<(), PrivateGiftError> {
+        fs::write(path, self.seal(key, rng))?;
+        Ok(())
+    }
+
+    /// Reads a sealed gift from `path` and opens it with `key`.
+    pub fn read_sealed(path: &Path, key: &[u8; GIFT_KEY_LEN]) -> Result<Self, PrivateGiftError> {
+        let blob = fs::read(path)?;
+        Self::open(&blob, key)
+    }
+
+    /// Imports the gift note into the recipient's client store.
+    ///
+    /// The store then tracks the note tag, so the next sync picks up the note commitment and the
+    /// note can be consumed as an authenticated input note with [`PrivateGift::secret`] as args.
+    pub async fn import<AUTH: TransactionAuthenticator + Sync + 'static>(
+        &self,
+        client: &mut Client<AUTH>,
+    ) -> Result<NoteId, PrivateGiftError> {
+        let note_file = NoteFile::NoteDetails {
+            details: NoteDetails::from(self.note.clone()),
+            after_block_num: self.after_block_num,
+            tag: Some(self.note.metadata().tag()),
+        };
+
+        Ok(client.import_note(note_file).await?)
+    }
+}
+
+impl Serializable for PrivateGift {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.note.write_into(target);
+        self.secret.write_into(target);
+        self.after_block_num.write_into(target);
+    }
+}
+
+impl Deserializable for PrivateGift {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let note = Note::read_from(source)?;
+        let secret = Word::read_from(source)?;
+        let after_block_num = BlockNumber::read_from(source)?;
+
+        Ok(Self {
+            note,
+            secret,
+            after_block_num,
+        })
+    }
+}
@@ -0,0 +1,499 @@
+//! ZoroSwap pool accounts.
+//!
+//! A pool account pairs two assets, pinned when it is deployed. It holds them in its vault and
+//! keeps one [`PoolState`] per asset in the storage map of zoro_pool.masm. ZoroSwap notes hand
+//! their asset to the pool, which computes the output amount from these states, applies the swap
+//! to the reserves and sends the payout, so neither the note nor the consumer chooses the payout.
+//! The pool rejects swaps of any other asset.
+//!
+//! The pool has no wallet component: assets only enter and leave its vault through the pool
+//! procedures, in the amounts they compute. The key of the pool account signs its transactions but
+//! cannot move the reserves or bypass the admin checks.
+//!
+//! The pool states can also be set by the pool admin, usually our multisig, through admin notes
+//! (see [`create_zoro_pool_admin_note`]), which also fund the reserves. The pool rejects admin notes
+//! sent by any other account.
+//!
+//! Liquidity providers add liquidity with deposit notes, which credit them shares of the pool, and
+//! remove it with withdrawal notes, which burn shares for a proportional part of the reserve. The
+//! liabilities of a pool are the shares issued for it. Only the admin initializes an empty pool,
+//! by setting its state and funding its reserve, so the first deposit cannot set the share price.
+
+use rand::RngCore;
+use rand::rngs::StdRng;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::time::sleep;
+
+use miden_client::account::{
+    Account, AccountBuilder, AccountId, AccountStorageMode, AccountType, StorageMap, StorageSlot,
+};
+use miden_client::asset::{Asset, FungibleAsset};
+use miden_client::auth::AuthSecretKey;
+use miden_client::crypto::SecretKey;
+use miden_client::keystore::FilesystemKeyStore;
+use miden_client::note::{
+    Note, NoteAssets, NoteExecutionHint, NoteInputs, NoteMetadata, NoteRecipient, NoteScript,
+    NoteTag, NoteType,
+};
+use miden_client::transaction::{OutputNote, TransactionRequestBuilder};
+use miden_client::{Client as MidenClient, ClientError, Felt, ScriptBuilder, Word, ZERO};
+use miden_lib::account::auth::AuthRpoFalcon512;
+use miden_lib::note::utils::build_p2id_recipient;
+use miden_lib::note::well_known_note::WellKnownNote;
+use miden_lib::transaction::TransactionKernel;
+use miden_objects::NoteError;
+use miden_objects::account::AccountComponent;
+use miden_objects::assembly::{Assembler, Library};
+
+use crate::common::{create_library_with_assembler, fund_account, wide_math_library, word_to_masm};
+use crate::constants::{
+    LP_SHARES_SLOT, POOL_ADMIN_SLOT, POOL_ASSETS_SLOT, POOL_STATE_SLOT, SYNC_STATE_WAIT_TIME,
+    ZORO_POOL_CODE_PATH, ZORO_POOL_LIBRARY_PATH,
+};
+
+type Client = MidenClient<FilesystemKeyStore<StdRng>>;
+
+/// State of the pool of one asset, as stored by zoro_pool.masm.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PoolState {
+    pub liabilities: u64,
+    pub reserve: u64,
+    pub reserve_with_slippage: u64,
+}
+
+impl PoolState {
+    /// Returns the storage map value `[liabilities, reserve, reserve_with_slippage, 0]`.
+    pub fn to_word(&self) -> Word {
+        Word::from([
+            Felt::new(self.liabilities),
+            Felt::new(self.reserve),
+            Felt::new(self.reserve_with_slippage),
+            ZERO,
+        ])
+    }
+
+    /// Returns the shares issued for a deposit of `amount`, as computed by zoro_pool.masm:
+    /// `amount * liabilities / reserve`, or `None` for an empty pool, which rejects deposits
+    /// until the admin initializes it.
+    pub fn deposit_shares(&self, amount: u64) -> Option<u64> {
+        if self.liabilities == 0 || self.reserve == 0 {
+            return None;
+        }
+        Some((amount as u128 * self.liabilities as u128 / self.reserve as u128) as u64)
+    }
+
+    /// Returns the reserve of the pool on the swap curve, as computed by zoro_pool.masm: the
+    /// reserve with slippage if it covers the liabilities,
+    /// `reserve_with_slippage * reserve_with_slippage / liabilities` otherwise.
+    pub fn virtual_reserve(&self) -> u64 {
+        if self.liabilities <= self.reserve_with_slippage {
+            return self.reserve_with_slippage;
+        }
+        (self.reserve_with_slippage as u128 * self.reserve_with_slippage as u128
+            / self.liabilities as u128) as u64
+    }
+
+    /// Returns the amount paid out for burning `shares`, as computed by zoro_pool.masm:
+    /// `shares * reserve / liabilities`.
+    pub fn withdrawal_amount(&self, shares: u64) -> u64 {
+        (shares as u128 * self.reserve as u128 / self.liabilities as u128) as u64
+    }
+
+    /// Reads a pool state from its storage map value.
+    pub fn from_word(word: Word) -> Self {
+        Self {
+            liabilities: word[0].as_int(),
+            reserve: word[1].as_int(),
+            reserve_with_slippage: word[2].as_int(),
+        }
+    }
+}
+
+/// Action carried by a pool admin note.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoolAdminAction {
+    /// Sets the state of the pool of the asset issued by `faucet_id`.
+    SetPoolState {
+        faucet_id: AccountId,
+        state: PoolState,
+    },
+    /// Hands the pool over to a new admin account.
+    SetAdmin(AccountId),
+    /// Adds the asset carried by the note to the pool reserves, leaving the pool states unchanged.
+    Fund(FungibleAsset),
+}
+
+impl PoolAdminAction {
+    /// Returns the admin note inputs `[action, 0, account_id_suffix, account_id_prefix,
+    /// POOL_STATE]`.
+    fn to_inputs(self) -> Vec<Felt> {
+        let (action, account_id, state) = match self {
+            Self::SetPoolState { faucet_id, state } => (0, faucet_id, state.to_word()),
+            Self::SetAdmin(admin) => (1, admin, Word::default()),
+            Self::Fund(asset) => (2, asset.faucet_id(), Word::default()),
+        };
+
+        let mut inputs = vec![
+            Felt::new(action),
+            ZERO,
+            account_id.suffix(),
+            account_id.prefix().as_felt(),
+        ];
+        inputs.extend(state.iter());
+        inputs
+    }
+}
+
+/// Returns the storage map key `[0, 0, faucet_id_suffix, faucet_id_prefix]` of the pool of the
+/// asset issued by `faucet_id`.
+pub fn pool_state_key(faucet_id: AccountId) -> Word {
+    Word::from([ZERO, ZERO, faucet_id.suffix(), faucet_id.prefix().as_felt()])
+}
+
+/// Returns the storage map key `[faucet_id_suffix, faucet_id_prefix, lp_id_suffix, lp_id_prefix]`
+/// of the shares of `lp_id` in the pool of the asset issued by `faucet_id`.
+pub fn lp_shares_key(faucet_id: AccountId, lp_id: AccountId) -> Word {
+    Word::from([
+        faucet_id.suffix(),
+        faucet_id.prefix().as_felt(),
+        lp_id.suffix(),
+        lp_id.prefix().as_felt(),
+    ])
+}
+
+/// Compiles the zoro_pool.masm library, needed to compile scripts calling the pool.
+pub fn zoro_pool_library() -> Library {
+    let account_code = fs::read_to_string(Path::new(ZORO_POOL_CODE_PATH)).unwrap();
+    create_library_with_assembler(zoro_pool_assembler(), account_code, ZORO_POOL_LIBRARY_PATH)
+        .unwrap()
+}
+
+/// Builds the zoro_pool.masm component administered by `admin`, pairing the assets of the two
+/// given pools and holding their initial states.
+///
+/// # Panics
+///
+/// Panics if both pools are for the same asset.
+pub fn zoro_pool_component(
+    admin: AccountId,
+    pools: [(AccountId, PoolState); 2],
+) -> AccountComponent {
+    let account_code = fs::read_to_string(Path::new(ZORO_POOL_CODE_PATH)).unwrap();
+
+    let [(faucet0_id, state0), (faucet1_id, state1)] = pools;
+    assert_ne!(faucet0_id, faucet1_id, "a pool pairs two different assets");
+
+    let mut pool_states = StorageMap::new();
+    pool_states.insert(pool_state_key(faucet0_id), state0.to_word());
+    pool_states.insert(pool_state_key(faucet1_id), state1.to_word());
+
+    AccountComponent::compile(
+        account_code,
+        zoro_pool_assembler(),
+        vec![
+            StorageSlot::Map(pool_states),
+            StorageSlot::Value(Word::from([
+                ZERO,
+                ZERO,
+                admin.suffix(),
+                admin.prefix().as_felt(),
+            ])),
+            StorageSlot::Map(StorageMap::new()),
+            StorageSlot::Value(Word::from([
+                faucet0_id.suffix(),
+                faucet0_id.prefix().as_felt(),
+                faucet1_id.suffix(),
+                faucet1_id.prefix().as_felt(),
+            ])),
+        ],
+    )
+    .unwrap()
+    .with_supports_all_types()
+}
+
+/// Creates a pool account administered by `admin`, pairing the assets of the two given pools with
+/// their initial states.
+///
+/// The pool has no wallet: it receives and pays out assets through the pool procedures only. Its
+/// reserves are expected to be funded up to the initial pool reserves.
+pub async fn create_zoro_pool_account(
+    client: &mut Client,
+    keystore: FilesystemKeyStore<StdRng>,
+    admin: AccountId,
+    pools: [(AccountId, PoolState); 2],
+) -> Result<(Account, SecretKey), ClientError> {
+    let mut init_seed = [0_u8; 32];
+    client.rng().fill_bytes(&mut init_seed);
+
+    let key_pair = SecretKey::with_rng(client.rng());
+    let (account, seed) = AccountBuilder::new(init_seed)
+        .account_type(AccountType::RegularAccountImmutableCode)
+        .storage_mode(AccountStorageMode::Public)
+        .with_auth_component(AuthRpoFalcon512::new(key_pair.public_key()))
+        .with_component(zoro_pool_component(admin, pools))
+        .build()
+        .unwrap();
+    client.add_account(&account, Some(seed), false).await?;
+    keystore
+        .add_key(&AuthSecretKey::RpoFalcon512(key_pair.clone()))
+        .unwrap();
+
+    Ok((account, key_pair))
+}
+
+/// Reads the state of the pool of the asset issued by `faucet_id` from the client store.
+pub async fn get_pool_state(
+    client: &Client,
+    pool_id: AccountId,
+    faucet_id: AccountId,
+) -> Result<PoolState, ClientError> {
+    let pool = client
+        .get_account(pool_id)
+        .await?
+        .expect("pool account not found");
+    let state = pool
+        .account()
+        .storage()
+        .get_map_item(POOL_STATE_SLOT, pool_state_key(faucet_id))
+        .unwrap();
+
+    Ok(PoolState::from_word(state))
+}
+
+/// Reads the two assets paired by the pool from the client store.
+pub async fn get_pool_assets(
+    client: &Client,
+    pool_id: AccountId,
+) -> Result<[AccountId; 2], ClientError> {
+    let pool = client
+        .get_account(pool_id)
+        .await?
+        .expect("pool account not found");
+    let assets = pool.account().storage().get_item(POOL_ASSETS_SLOT).unwrap();
+
+    Ok([
+        AccountId::try_from([assets[1], assets[0]]).unwrap(),
+        AccountId::try_from([assets[3], assets[2]]).unwrap(),
+    ])
+}
+
+/// Reads the admin account of the pool from the client store.
+pub async fn get_pool_admin(client: &Client, pool_id: AccountId) -> Result<AccountId, ClientError> {
+    let pool = client
+        .get_account(pool_id)
+        .await?
+        .expect("pool account not found");
+    let admin = pool.account().storage().get_item(POOL_ADMIN_SLOT).unwrap();
+
+    Ok(AccountId::try_from([admin[3], admin[2]]).unwrap())
+}
+
+/// Reads the shares of `lp_id` in the pool of the asset issued by `faucet_id` from the client
+/// store.
+pub async fn get_lp_shares(
+    client: &Client,
+    pool_id: AccountId,
+    faucet_id: AccountId,
+    lp_id: AccountId,
+) -> Result<u64, ClientError> {
+    let pool = client
+        .get_account(pool_id)
+        .await?
+        .expect("pool account not found");
+    let shares = pool
+        .account()
+        .storage()
+        .get_map_item(LP_SHARES_SLOT, lp_shares_key(faucet_id, lp_id))
+        .unwrap();
+
+    Ok(shares[0].as_int())
+}
+
+/// Builds an admin note from `admin` applying `action` to the pool `pool_id`.
+///
+/// The note is tagged for the pool account, which has to consume it to apply the action. Funding
+/// notes carry the funded asset.
+pub fn create_zoro_pool_admin_note(
+    admin: AccountId,
+    pool_id: AccountId,
+    action: PoolAdminAction,
+    serial_num: Word,
+) -> Result<Note, NoteError> {
+    let recipient = NoteRecipient::new(
+        serial_num,
+        zoro_pool_admin_script(),
+        NoteInputs::new(action.to_inputs())?,
+    );
+    let assets = match action {
+        PoolAdminAction::Fund(asset) => NoteAssets::new(vec![asset.into()])?,
+        _ => NoteAssets::default(),
+    };
+
+    Ok(Note::new(
+        assets,
+        pool_note_metadata(admin, pool_id)?,
+        recipient,
+    ))
+}
+
+/// Funds the reserves of the pool `pool_id` with `amount` tokens of `faucet`.
+///
+/// The pool has no wallet, so the tokens are minted to the pool admin `admin`, which sends them to
+/// the pool in a [`PoolAdminAction::Fund`] admin note. The pool states are left unchanged.
+pub async fn fund_zoro_pool(
+    client: &mut Client,
+    faucet: &Account,
+    admin: &Account,
+    pool_id: AccountId,
+    amount: u64,
+) -> Result<(), ClientError> {
+    fund_account(client, faucet, admin, amount).await?;
+
+    let asset = FungibleAsset::new(faucet.id(), amount).unwrap();
+    let serial_num = client.rng().inner_mut().draw_word();
+    let fund_note = create_zoro_pool_admin_note(
+        admin.id(),
+        pool_id,
+        PoolAdminAction::Fund(asset),
+        serial_num,
+    )?;
+
+    let tx_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(fund_note.clone())])
+        .build()?;
+    let tx_exec = client.new_transaction(admin.id(), tx_request).await?;
+    client.submit_transaction(tx_exec).await?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    let consume_req = TransactionRequestBuilder::new()
+        .unauthenticated_input_notes([(fund_note, None)])
+        .build()?;
+    let tx_exec = client.new_transaction(pool_id, consume_req).await?;
+    client.submit_transaction(tx_exec).await?;
+    client.sync_state().await?;
+
+    Ok(())
+}
+
+/// Builds a note from `lp` depositing `asset` into the pool `pool_id`.
+///
+/// Only the pool can consume the note, which credits `lp` the shares of the deposit.
+pub fn create_lp_deposit_note(
+    lp: AccountId,
+    pool_id: AccountId,
+    asset: Asset,
+    serial_num: Word,
+) -> Result<Note, NoteError> {
+    let inputs = NoteInputs::new(vec![pool_id.suffix(), pool_id.prefix().as_felt()])?;
+    let recipient = NoteRecipient::new(
+        serial_num,
+        compile_pool_note_script("lp_deposit.masm"),
+        inputs,
+    );
+
+    Ok(Note::new(
+        NoteAssets::new(vec![asset])?,
+        pool_note_metadata(lp, pool_id)?,
+        recipient,
+    ))
+}
+
+/// Builds a note from `lp` withdrawing `shares` from the pool of the asset issued by `faucet_id`
+/// in the pool `pool_id`.
+///
+/// Only the pool can consume the note, which burns the shares and pays `lp` back in a P2ID note
+/// (see [`lp_withdraw_p2id_recipient`]).
+pub fn create_lp_withdraw_note(
+    lp: AccountId,
+    pool_id: AccountId,
+    faucet_id: AccountId,
+    shares: u64,
+    serial_num: Word,
+) -> Result<Note, NoteError> {
+    let inputs = NoteInputs::new(vec![
+        // [shares, 0, faucet_id_suffix, faucet_id_prefix]
+        Felt::new(shares),
+        ZERO,
+        faucet_id.suffix(),
+        faucet_id.prefix().as_felt(),
+        // [pool_id_suffix, pool_id_prefix, p2id_tag, 0]
+        pool_id.suffix(),
+        pool_id.prefix().as_felt(),
+        Felt::from(NoteTag::from_account_id(lp)),
+        ZERO,
+    ])?;
+    let recipient = NoteRecipient::new(
+        serial_num,
+        compile_pool_note_script("lp_withdraw.masm"),
+        inputs,
+    );
+
+    Ok(Note::new(
+        NoteAssets::default(),
+        pool_note_metadata(lp, pool_id)?,
+        recipient,
+    ))
+}
+
+/// Returns the recipient of the P2ID note paying out a withdrawal note.
+///
+/// The P2ID note pays the sender of the withdrawal note and uses its serial number with the last
+/// element incremented.
+pub fn lp_withdraw_p2id_recipient(withdraw_note: &Note) -> Result<NoteRecipient, NoteError> {
+    let serial_num = withdraw_note.serial_num();
+    let p2id_serial_num = Word::from([
+        serial_num[0],
+        serial_num[1],
+        serial_num[2],
+        serial_num[3] + Felt::new(1),
+    ]);
+    build_p2id_recipient(withdraw_note.metadata().sender(), p2id_serial_num)
+}
+
+/// Compiles the pool admin note script, linking it to the pool account procedures.
+pub fn zoro_pool_admin_script() -> NoteScript {
+    compile_pool_note_script("zoro_pool_admin.masm")
+}
+
+// The pool computes shares and swap outputs with wide_math.masm, which is linked statically into
+// its code
+fn zoro_pool_assembler() -> Assembler {
+    TransactionKernel::assembler()
+        .with_debug_mode(true)
+        .with_static_library(&wide_math_library())
+        .unwrap()
+}
+
+// Notes sent to the pool are public and tagged for the pool account
+fn pool_note_metadata(sender: AccountId, pool_id: AccountId) -> Result<NoteMetadata, NoteError> {
+    NoteMetadata::new(
+        sender,
+        NoteType::Public,
+        NoteTag::from_account_id(pool_id),
+        NoteExecutionHint::always(),
+        Felt::new(0),
+    )
+}
+
+// Compiles a note script calling the pool account procedures, injecting the P2ID script root
+// used by notes paying out of the pool
+fn compile_pool_note_script(file_name: &str) -> NoteScript {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let path: PathBuf = [manifest_dir, "masm", "notes", file_name].iter().collect();
+    let note_code = fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("Error reading {}: {}", path.display(), err))
+        .replace(
+            "{{P2ID_SCRIPT_ROOT}}",
+            &word_to_masm(WellKnownNote::P2ID.script_root()),
+        );
+
+    ScriptBuilder::new(true)
+        .with_dynamically_linked_library(&zoro_pool_library())
+        .unwrap()
+        .compile_note_script(note_code)
+        .unwrap()
+}
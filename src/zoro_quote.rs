@@ -0,0 +1,199 @@
+//! Local quotes of ZoroSwap notes.
+//!
+//! Reproduces the swap computation of zoro_pool.masm in Rust, so that the output of a swap note can be
+//! known before it is submitted: the output amount on the curve of the virtual reserves (see
+//! [`PoolState::virtual_reserve`]), its price impact, whether the note would be refunded and the
+//! pool states after the swap.
+
+use thiserror::Error;
+
+use miden_client::block::BlockNumber;
+
+use crate::zoro_pool::PoolState;
+use crate::zoro_swap::{ZoroSwapNote, ZoroSwapOutcome};
+
+/// Price impacts are given in basis points.
+pub const BPS: u64 = 10_000;
+
+// Modulus of the field the amounts live in
+const FIELD_MODULUS: u128 = 0xffff_ffff_0000_0001;
+
+// Smallest quotient wide_math.masm rejects as not fitting into a field element
+const MAX_QUOTIENT: u64 = 0xffff_ffff_0000_0000;
+
+/// Represents the cases in which the swap script fails, so that no quote exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum QuoteError {
+    #[error("swap amounts overflow the field")]
+    /// The input virtual reserve plus the input amount, the output amount, or the input reserves
+    /// credited the input amount do not fit into a field element.
+    Overflow,
+    #[error("the virtual reserve of the input or output pool is zero")]
+    /// The pool cannot price the swap.
+    EmptyPool,
+}
+
+/// Reason for which a swap note is refunded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefundReason {
+    /// The swap note is consumed after its deadline.
+    DeadlinePassed,
+    /// The output amount is lower than the minimum output amount, or zero.
+    MinAmountOutNotMet,
+    /// The pool of the requested asset holds less than the output amount.
+    InsufficientReserve,
+}
+
+/// Quote of a swap note against given pool states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZoroSwapQuote {
+    /// Output amount on the curve; 0 if the deadline has passed, as the pool does not compute it.
+    pub amount_out: u64,
+    /// Shortfall of the swap price against the spot price `virtual_reserve_out /
+    /// virtual_reserve_in`, in basis points, rounded up.
+    pub price_impact_bps: u64,
+    /// Outcome read from the P2ID output note.
+    pub outcome: ZoroSwapOutcome,
+    /// Why the swap is refunded, `None` if it is executed.
+    pub refund_reason: Option<RefundReason>,
+    /// State of the pool of the offered asset after the swap.
+    pub pool_in_after: PoolState,
+    /// State of the pool of the requested asset after the swap.
+    pub pool_out_after: PoolState,
+}
+
+/// Returns the output amount of a swap of `amount_in`, as computed by zoro_pool.masm:
+/// `out.virtual_reserve() * amount_in / (in.virtual_reserve() + amount_in)`.
+///
+/// The product is computed on 128 bits.
+pub fn quote_amount_out(
+    amount_in: u64,
+    pool_in: &PoolState,
+    pool_out: &PoolState,
+) -> Result<u64, QuoteError> {
+    let virtual_reserve_in = pool_in.virtual_reserve();
+    let virtual_reserve_out = pool_out.virtual_reserve();
+    if virtual_reserve_in == 0 || virtual_reserve_out == 0 {
+        return Err(QuoteError::EmptyPool);
+    }
+
+    let denominator = virtual_reserve_in as u128 + amount_in as u128;
+    if denominator >= FIELD_MODULUS {
+        return Err(QuoteError::Overflow);
+    }
+
+    let amount_out = virtual_reserve_out as u128 * amount_in as u128 / denominator;
+    // the output never exceeds the output virtual reserve, so it fits into u64
+    let amount_out = amount_out as u64;
+    if amount_out >= MAX_QUOTIENT {
+        return Err(QuoteError::Overflow);
+    }
+    Ok(amount_out)
+}
+
+/// Returns the outputs `(amount_mid, amount_out)` of a route of `amount_in` through an
+/// intermediate asset, as computed by the pool accounts of its two hops.
+///
+/// `first` holds the states of the pools of the offered and intermediate assets in the pool
+/// account of the first hop, `second` those of the intermediate and requested assets in the pool
+/// account of the second hop. Each hop is a ZoroSwap note on its own pool account, so the first
+/// hop leaves the states of the second pool account unchanged.
+pub fn quote_route_amounts(
+    amount_in: u64,
+    first: (&PoolState, &PoolState),
+    second: (&PoolState, &PoolState),
+) -> Result<(u64, u64), QuoteError> {
+    let amount_mid = quote_amount_out(amount_in, first.0, first.1)?;
+    let amount_out = quote_amount_out(amount_mid, second.0, second.1)?;
+    Ok((amount_mid, amount_out))
+}
+
+/// Returns the price impact in basis points of a swap of `amount_in` for `amount_out`.
+pub fn price_impact_bps(
+    amount_in: u64,
+    amount_out: u64,
+    pool_in: &PoolState,
+    pool_out: &PoolState,
+) -> u64 {
+    let virtual_reserve_out = pool_out.virtual_reserve();
+    if amount_in == 0 || virtual_reserve_out == 0 {
+        return 0;
+    }
+    // amount_out / amount_in relative to the spot price, in basis points
+    let numerator = amount_out as u128 * pool_in.virtual_reserve() as u128;
+    let denominator = amount_in as u128 * virtual_reserve_out as u128;
+    // for large amounts, drop the low bits of both terms so that numerator * BPS fits into u128
+    let shift = (128 - numerator.max(denominator).leading_zeros()).saturating_sub(114);
+    let relative_price = (numerator >> shift) * BPS as u128 / (denominator >> shift).max(1);
+    BPS.saturating_sub(relative_price.min(BPS as u128) as u64)
+}
+
+/// Quotes `swap` as if it were consumed in block `block_num` by a pool account holding the pool
+/// states `pool_in` and `pool_out` of the offered and requested assets.
+///
+/// The quote follows the pool step by step, so it fails exactly when consuming the note fails on
+/// the pool math.
+pub fn quote_swap(
+    swap: &ZoroSwapNote,
+    pool_in: PoolState,
+    pool_out: PoolState,
+    block_num: BlockNumber,
+) -> Result<ZoroSwapQuote, QuoteError> {
+    let offered_asset = swap.offered_asset();
+    let amount_in = offered_asset.unwrap_fungible().amount();
+    let refunded = |amount_out, price_impact_bps, reason| ZoroSwapQuote {
+        amount_out,
+        price_impact_bps,
+        outcome: ZoroSwapOutcome::Refunded(offered_asset),
+        refund_reason: Some(reason),
+        pool_in_after: pool_in,
+        pool_out_after: pool_out,
+    };
+
+    // the swap can still be executed in the deadline block itself
+    if block_num > swap.deadline() {
+        return Ok(refunded(0, 0, RefundReason::DeadlinePassed));
+    }
+
+    let amount_out = quote_amount_out(amount_in, &pool_in, &pool_out)?;
+    let price_impact_bps = price_impact_bps(amount_in, amount_out, &pool_in, &pool_out);
+    // the pool pays nothing out of a swap buying nothing, it refunds it
+    if amount_out < swap.min_amount_out() || amount_out == 0 {
+        return Ok(refunded(
+            amount_out,
+            price_impact_bps,
+            RefundReason::MinAmountOutNotMet,
+        ));
+    }
+    if pool_out.reserve < amount_out {
+        return Ok(refunded(
+            amount_out,
+            price_impact_bps,
+            RefundReason::InsufficientReserve,
+        ));
+    }
+
+    // the pool of the offered asset is credited amount_in, the other one debited amount_out
+    let credit = |value: u64| {
+        value
+            .checked_add(amount_in)
+            .filter(|sum| (*sum as u128) < FIELD_MODULUS)
+            .ok_or(QuoteError::Overflow)
+    };
+    Ok(ZoroSwapQuote {
+        amount_out,
+        price_impact_bps,
+        outcome: ZoroSwapOutcome::Executed { amount_out },
+        refund_reason: None,
+        pool_in_after: PoolState {
+            reserve: credit(pool_in.reserve)?,
+            reserve_with_slippage: credit(pool_in.reserve_with_slippage)?,
+            ..pool_in
+        },
+        pool_out_after: PoolState {
+            reserve: pool_out.reserve - amount_out,
+            reserve_with_slippage: pool_out.reserve_with_slippage - amount_out,
+            ..pool_out
+        },
+    })
+}
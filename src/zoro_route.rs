@@ -0,0 +1,297 @@
+//! ZoroSwap routes.
+//!
+//! A route swaps one asset into another through an intermediate asset, in two hops run by two
+//! distinct pool accounts. Both hops are ZoroSwap notes (see [`crate::zoro_swap`]): the creator
+//! sends the note of the first hop, pinned to the first pool, whose output is paid into the note of
+//! the second hop, pinned to the second pool, instead of a P2ID note. The second hop pays the
+//! requested asset in a P2ID note to the payout account.
+//!
+//! The transaction executing the first hop quotes both hops: the first one on the states of its
+//! own pool, the second one on the states of the second pool, read by foreign procedure
+//! invocation. If either hop would miss its minimum output, the offered asset is refunded to the
+//! creator and the second hop is never created.
+//!
+//! A transaction only updates one account, so the second hop is executed by the second pool in
+//! its own transaction. Should its pool states change in between so that it misses its minimum,
+//! the intermediate asset is refunded to the creator. Until a hop is executed, the creator can
+//! cancel it by consuming its note.
+
+use alloc::vec::Vec;
+use thiserror::Error;
+
+use miden_client::account::AccountId;
+use miden_client::asset::Asset;
+use miden_client::block::BlockNumber;
+use miden_client::note::{Note, NoteRecipient};
+use miden_client::transaction::{
+    OutputNote, TransactionRequest, TransactionRequestBuilder, TransactionRequestError,
+};
+use miden_client::{Felt, Word, ZERO};
+
+use crate::zoro_pool::PoolState;
+use crate::zoro_quote::{quote_amount_out, quote_route_amounts};
+use crate::zoro_swap::{ZoroSwapArgs, ZoroSwapError, ZoroSwapNote, zoro_swap_recipient};
+
+/// Represents errors that can occur while building or following routes.
+#[derive(Debug, Error)]
+pub enum ZoroRouteError {
+    #[error("both hops of the route run on pool account {0}")]
+    /// The two hops name the same pool account.
+    SamePool(AccountId),
+    #[error("note is not the second hop of the route")]
+    /// The note is not the ZoroSwap note paid by the first hop of the route.
+    NotASecondHop,
+    #[error("failed to build route hop: {0}")]
+    /// A hop note could not be built or read.
+    Swap(#[from] ZoroSwapError),
+    #[error("failed to build ZoroRoute request: {0}")]
+    /// A transaction request could not be built.
+    Request(#[from] TransactionRequestError),
+}
+
+/// One hop of a route: the pool account running it, the asset it buys and the minimum amount it
+/// has to buy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouteHop {
+    pub pool: AccountId,
+    pub faucet_id: AccountId,
+    pub min_amount_out: u64,
+}
+
+/// Best path found by [`find_best_route`] and the amounts it yields on the local pool states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZoroRoutePlan {
+    /// Pool account of the direct swap, or of the first hop of a route.
+    pub first_pool: AccountId,
+    /// Pool account of the second hop, `None` if the direct swap is the best path.
+    pub second_pool: Option<AccountId>,
+    /// Faucet of the intermediate asset, `None` if the direct swap is the best path.
+    pub mid_faucet: Option<AccountId>,
+    /// Output of the first hop, 0 for a direct swap.
+    pub amount_mid: u64,
+    /// Amount of the requested asset bought.
+    pub amount_out: u64,
+}
+
+/// A route: the ZoroSwap note of its first hop and the recipient of the ZoroSwap note of its
+/// second hop, whose asset is only known once the first hop is executed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZoroRoute {
+    first_hop: ZoroSwapNote,
+    second_hop: RouteHop,
+    second_hop_recipient: NoteRecipient,
+}
+
+impl ZoroRoute {
+    /// Builds a route from `creator` swapping `offered_asset` with the hop `first` for the
+    /// intermediate asset, then with the hop `second` for the requested asset, executable until
+    /// block `deadline`. The output is paid to the creator.
+    pub fn new(
+        creator: AccountId,
+        offered_asset: Asset,
+        first: RouteHop,
+        second: RouteHop,
+        deadline: BlockNumber,
+        serial_num: Word,
+    ) -> Result<Self, ZoroRouteError> {
+        Self::with_payout(
+            creator,
+            offered_asset,
+            first,
+            second,
+            deadline,
+            creator,
+            ZERO,
+            serial_num,
+        )
+    }
+
+    /// Builds a route like [`Self::new`], whose P2ID output pays `payout` and carries `aux` in its
+    /// metadata.
+    ///
+    /// The first hop uses `serial_num` and the second hop `serial_num` with 2 added to its last
+    /// element, so that it does not collide with the P2ID outputs of the first hop.
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_payout(
+        creator: AccountId,
+        offered_asset: Asset,
+        first: RouteHop,
+        second: RouteHop,
+        deadline: BlockNumber,
+        payout: AccountId,
+        aux: Felt,
+        serial_num: Word,
+    ) -> Result<Self, ZoroRouteError> {
+        if first.pool == second.pool {
+            return Err(ZoroRouteError::SamePool(first.pool));
+        }
+
+        let second_hop_recipient = zoro_swap_recipient(
+            creator,
+            second.pool,
+            second.faucet_id,
+            second.min_amount_out,
+            deadline,
+            payout,
+            aux,
+            Word::from([
+                serial_num[0],
+                serial_num[1],
+                serial_num[2],
+                serial_num[3] + Felt::new(2),
+            ]),
+        )?;
+        let first_hop = ZoroSwapNote::with_next_hop(
+            creator,
+            first.pool,
+            offered_asset,
+            first.faucet_id,
+            first.min_amount_out,
+            deadline,
+            second,
+            &second_hop_recipient,
+            aux,
+            serial_num,
+        )?;
+
+        Ok(Self {
+            first_hop,
+            second_hop: second,
+            second_hop_recipient,
+        })
+    }
+
+    /// Returns the ZoroSwap note of the first hop, sent by the creator to start the route.
+    pub fn first_hop(&self) -> &ZoroSwapNote {
+        &self.first_hop
+    }
+
+    /// Returns the second hop, buying the requested asset.
+    pub fn second_hop(&self) -> RouteHop {
+        self.second_hop
+    }
+
+    /// Returns the recipient of the ZoroSwap note of the second hop.
+    pub fn second_hop_recipient(&self) -> &NoteRecipient {
+        &self.second_hop_recipient
+    }
+
+    /// Builds the request consuming the note of the first hop for its pool account, reading the
+    /// pool account of the second hop to quote it.
+    ///
+    /// Unlike [`ZoroSwapNote::consume_request`], the note of the second hop is created with its
+    /// details, so that it can be passed on to the pool account of the second hop.
+    pub fn first_hop_request(
+        &self,
+        args: ZoroSwapArgs,
+    ) -> Result<TransactionRequest, ZoroRouteError> {
+        Ok(TransactionRequestBuilder::new()
+            .unauthenticated_input_notes([(self.first_hop.note().clone(), Some(args.to_word()))])
+            .expected_output_recipients(vec![
+                self.second_hop_recipient.clone(),
+                self.first_hop.refund_recipient()?,
+            ])
+            .foreign_accounts(self.first_hop.next_hop_foreign_account()?)
+            .build()?)
+    }
+
+    /// Returns the ZoroSwap note of the second hop from the note created by the first hop. It is
+    /// consumed by the pool account of the second hop with [`ZoroSwapNote::consume_request`].
+    pub fn second_hop_note(&self, output: &OutputNote) -> Result<ZoroSwapNote, ZoroRouteError> {
+        let assets = output.assets().ok_or(ZoroRouteError::NotASecondHop)?;
+        let note = Note::new(
+            assets.clone(),
+            *output.metadata(),
+            self.second_hop_recipient.clone(),
+        );
+        if note.id() != output.id() {
+            return Err(ZoroRouteError::NotASecondHop);
+        }
+
+        Ok(ZoroSwapNote::from_note(note)?)
+    }
+}
+
+/// Chooses the path buying the most of the asset of `out_faucet` for `amount_in` tokens of the
+/// asset of `in_faucet`, among the direct swaps on each pool account and the routes through any
+/// other asset across two distinct pool accounts.
+///
+/// `pools` holds the pool states of each pool account, per faucet. Paths are quoted on these
+/// states with the pool math; paths on which a pool cannot pay or that buy nothing are skipped.
+/// The direct swap wins ties. Returns `None` if no path is left.
+pub fn find_best_route(
+    amount_in: u64,
+    in_faucet: AccountId,
+    out_faucet: AccountId,
+    pools: &[(AccountId, Vec<(AccountId, PoolState)>)],
+) -> Option<ZoroRoutePlan> {
+    let pool = |states: &[(AccountId, PoolState)], faucet_id: AccountId| {
+        states
+            .iter()
+            .find(|(id, _)| *id == faucet_id)
+            .map(|(_, state)| *state)
+    };
+
+    let mut best: Option<ZoroRoutePlan> = None;
+    let mut consider = |plan: ZoroRoutePlan| {
+        if plan.amount_out > 0 && best.is_none_or(|best| plan.amount_out > best.amount_out) {
+            best = Some(plan);
+        }
+    };
+
+    for (pool_account, states) in pools {
+        let (Some(pool_in), Some(pool_out)) = (pool(states, in_faucet), pool(states, out_faucet))
+        else {
+            continue;
+        };
+        if let Ok(amount_out) = quote_amount_out(amount_in, &pool_in, &pool_out) {
+            if amount_out <= pool_out.reserve {
+                consider(ZoroRoutePlan {
+                    first_pool: *pool_account,
+                    second_pool: None,
+                    mid_faucet: None,
+                    amount_mid: 0,
+                    amount_out,
+                });
+            }
+        }
+    }
+
+    for (first_pool, first_states) in pools {
+        let Some(pool_in) = pool(first_states, in_faucet) else {
+            continue;
+        };
+        for (mid_faucet, pool_mid) in first_states {
+            if *mid_faucet == in_faucet || *mid_faucet == out_faucet {
+                continue;
+            }
+            for (second_pool, second_states) in pools {
+                if second_pool == first_pool {
+                    continue;
+                }
+                let (Some(second_mid), Some(pool_out)) = (
+                    pool(second_states, *mid_faucet),
+                    pool(second_states, out_faucet),
+                ) else {
+                    continue;
+                };
+                let Ok((amount_mid, amount_out)) =
+                    quote_route_amounts(amount_in, (&pool_in, pool_mid), (&second_mid, &pool_out))
+                else {
+                    continue;
+                };
+                if amount_mid <= pool_mid.reserve && amount_out <= pool_out.reserve {
+                    consider(ZoroRoutePlan {
+                        first_pool: *first_pool,
+                        second_pool: Some(*second_pool),
+                        mid_faucet: Some(*mid_faucet),
+                        amount_mid,
+                        amount_out,
+                    });
+                }
+            }
+        }
+    }
+
+    best
+}
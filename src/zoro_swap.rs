@@ -0,0 +1,623 @@
+//! ZoroSwap notes.
+//!
+//! A ZoroSwap note offers one asset for at least `min_amount_out` tokens of another asset until a
+//! deadline block. It is consumed by the pool account chosen by its creator (see
+//! [`crate::zoro_pool`]), which receives the offered asset, computes the output amount from its
+//! pool states and sends the output note itself; the note fails for any other consumer. If the
+//! swap is executed, the output is paid in a P2ID note to the payout account, the creator unless
+//! specified otherwise, or into the swap note of the next hop of a route; if it is refunded, the
+//! offered asset goes back to the creator in a P2ID note. Until then, the creator can cancel the
+//! swap by consuming the note itself.
+//!
+//! A swap paying into a next hop first quotes that hop on the states of its pool, read by foreign
+//! procedure invocation, and is refunded if the next hop would miss its own minimum output.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use std::fs;
+use std::path::PathBuf;
+use thiserror::Error;
+
+use miden_client::account::AccountId;
+use miden_client::asset::Asset;
+use miden_client::auth::TransactionAuthenticator;
+use miden_client::block::BlockNumber;
+use miden_client::note::{
+    Note, NoteAssets, NoteExecutionHint, NoteExecutionMode, NoteId, NoteInputs, NoteMetadata,
+    NoteRecipient, NoteScript, NoteTag, NoteType,
+};
+use miden_client::rpc::domain::account::AccountStorageRequirements;
+use miden_client::store::NoteFilter;
+use miden_client::transaction::{
+    ForeignAccount, OutputNote, TransactionRequest, TransactionRequestBuilder,
+    TransactionRequestError,
+};
+use miden_client::{Client, ClientError, Felt, ScriptBuilder, Word, ZERO};
+use miden_lib::note::utils::build_p2id_recipient;
+use miden_lib::note::well_known_note::WellKnownNote;
+use miden_objects::NoteError;
+
+use crate::common::word_to_masm;
+use crate::constants::POOL_STATE_SLOT;
+use crate::zoro_pool::{pool_state_key, zoro_pool_library};
+use crate::zoro_route::RouteHop;
+
+/// Number of note inputs of a ZoroSwap note.
+pub const ZOROSWAP_NUM_INPUTS: usize = 24;
+
+/// Represents errors that can occur while building or reading ZoroSwap notes.
+#[derive(Debug, Error)]
+pub enum ZoroSwapError {
+    #[error("note is not a ZoroSwap note")]
+    /// The note does not run the ZoroSwap script or has malformed inputs.
+    NotAZoroSwap,
+    #[error("note is not the P2ID output of the swap")]
+    /// The note is not the P2ID note created when consuming the swap note.
+    NotASwapOutput,
+    #[error("failed to build ZoroSwap note: {0}")]
+    /// A note could not be built.
+    Note(#[from] NoteError),
+    #[error("failed to build ZoroSwap request: {0}")]
+    /// A transaction request could not be built.
+    Request(#[from] TransactionRequestError),
+    #[error("ZoroSwap client error: {0}")]
+    /// The client store could not be read.
+    Client(#[from] ClientError),
+}
+
+/// Note args of a ZoroSwap note, passed by the pool account consuming it.
+///
+/// The script reads a single word, `[expected_amount_out, 0, 0, 0]`. The pool states and the
+/// output amount are not part of the args: the pool account computes the output amount from its
+/// own pool states.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ZoroSwapArgs {
+    /// Output amount the consumer expects. The transaction fails if the pool pays out a different
+    /// amount, a refund paying out nothing; `None` skips the check.
+    pub expected_amount_out: Option<u64>,
+}
+
+impl ZoroSwapArgs {
+    /// Returns the note args word `[expected_amount_out, 0, 0, 0]`, 0 meaning no expectation.
+    pub fn to_word(&self) -> Word {
+        Word::from([
+            Felt::new(self.expected_amount_out.unwrap_or(0)),
+            ZERO,
+            ZERO,
+            ZERO,
+        ])
+    }
+
+    /// Reads the args back from a note args word.
+    pub fn from_word(word: Word) -> Self {
+        let expected_amount_out = word[0].as_int();
+        Self {
+            expected_amount_out: (expected_amount_out != 0).then_some(expected_amount_out),
+        }
+    }
+}
+
+/// Outcome of a consumed ZoroSwap note, as read from its P2ID output note.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZoroSwapOutcome {
+    /// The swap was executed and the payout account is paid `amount_out` tokens of the requested
+    /// asset.
+    Executed { amount_out: u64 },
+    /// The deadline passed, the minimum output was not met or the pool could not pay: the
+    /// offered asset goes back to the creator.
+    Refunded(Asset),
+}
+
+/// A ZoroSwap note together with the parameters encoded in its inputs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZoroSwapNote {
+    note: Note,
+    creator: AccountId,
+    pool: AccountId,
+    requested_faucet: AccountId,
+    min_amount_out: u64,
+    deadline: BlockNumber,
+    payout: AccountId,
+    next_hop: Option<(Word, RouteHop)>,
+    aux: Felt,
+}
+
+impl ZoroSwapNote {
+    /// Builds a ZoroSwap note from `creator` offering `offered_asset` for at least
+    /// `min_amount_out` tokens of `requested_faucet`, executable by the pool account `pool` until
+    /// block `deadline`.
+    ///
+    /// The output is paid to the creator; see [`Self::with_payout`] to pay another account.
+    pub fn new(
+        creator: AccountId,
+        pool: AccountId,
+        offered_asset: Asset,
+        requested_faucet: AccountId,
+        min_amount_out: u64,
+        deadline: BlockNumber,
+        serial_num: Word,
+    ) -> Result<Self, ZoroSwapError> {
+        Self::with_payout(
+            creator,
+            pool,
+            offered_asset,
+            requested_faucet,
+            min_amount_out,
+            deadline,
+            creator,
+            ZERO,
+            serial_num,
+        )
+    }
+
+    /// Builds a ZoroSwap note like [`Self::new`], whose P2ID output pays `payout` and carries
+    /// `aux` in its metadata, e.g. a memo identifying the swap.
+    ///
+    /// Refunds still go to `creator`, with the same aux. Only `creator` can cancel the swap.
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_payout(
+        creator: AccountId,
+        pool: AccountId,
+        offered_asset: Asset,
+        requested_faucet: AccountId,
+        min_amount_out: u64,
+        deadline: BlockNumber,
+        payout: AccountId,
+        aux: Felt,
+        serial_num: Word,
+    ) -> Result<Self, ZoroSwapError> {
+        // the P2ID output is tagged for the payout account, as standard P2ID notes are
+        let inputs = swap_inputs(
+            creator,
+            pool,
+            requested_faucet,
+            min_amount_out,
+            deadline,
+            payout,
+            NoteTag::from_account_id(payout),
+            aux,
+            None,
+        )?;
+        let note = swap_note(creator, offered_asset, inputs, serial_num)?;
+
+        Ok(Self {
+            note,
+            creator,
+            pool,
+            requested_faucet,
+            min_amount_out,
+            deadline,
+            payout,
+            next_hop: None,
+            aux,
+        })
+    }
+
+    /// Builds a ZoroSwap note like [`Self::new`], whose output is paid into a note with the
+    /// recipient `next_hop_recipient` instead of a P2ID note: the ZoroSwap note of the hop
+    /// `next_hop` of a route (see [`crate::zoro_route`]). That note is sent by the pool, under the
+    /// ZoroSwap tag and with `aux` in its metadata.
+    ///
+    /// The swap is refunded to `creator` in a P2ID note if `next_hop`, quoted on the states of its
+    /// pool when the swap is executed, would buy less than its minimum output.
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_next_hop(
+        creator: AccountId,
+        pool: AccountId,
+        offered_asset: Asset,
+        requested_faucet: AccountId,
+        min_amount_out: u64,
+        deadline: BlockNumber,
+        next_hop: RouteHop,
+        next_hop_recipient: &NoteRecipient,
+        aux: Felt,
+        serial_num: Word,
+    ) -> Result<Self, ZoroSwapError> {
+        // the payout account of the inputs is unused, it is set to the creator
+        let inputs = swap_inputs(
+            creator,
+            pool,
+            requested_faucet,
+            min_amount_out,
+            deadline,
+            creator,
+            zoro_swap_tag(),
+            aux,
+            Some((next_hop_recipient.digest(), next_hop)),
+        )?;
+        let note = swap_note(creator, offered_asset, inputs, serial_num)?;
+
+        Ok(Self {
+            note,
+            creator,
+            pool,
+            requested_faucet,
+            min_amount_out,
+            deadline,
+            payout: creator,
+            next_hop: Some((next_hop_recipient.digest(), next_hop)),
+            aux,
+        })
+    }
+
+    /// Reads the swap parameters back from a ZoroSwap note, e.g. one found by a pool operator.
+    ///
+    /// The note may have been sent by another account than its creator, as the second hop of a
+    /// route is sent by the pool of the first hop.
+    pub fn from_note(note: Note) -> Result<Self, ZoroSwapError> {
+        if note.script().root() != zoro_swap_script().root() || note.assets().num_assets() != 1 {
+            return Err(ZoroSwapError::NotAZoroSwap);
+        }
+        let inputs = note.recipient().inputs().values();
+        if inputs.len() != ZOROSWAP_NUM_INPUTS {
+            return Err(ZoroSwapError::NotAZoroSwap);
+        }
+
+        let requested_faucet =
+            AccountId::try_from([inputs[3], inputs[2]]).map_err(|_| ZoroSwapError::NotAZoroSwap)?;
+        let deadline =
+            u32::try_from(inputs[4].as_int()).map_err(|_| ZoroSwapError::NotAZoroSwap)?;
+        let payout =
+            AccountId::try_from([inputs[9], inputs[8]]).map_err(|_| ZoroSwapError::NotAZoroSwap)?;
+        let creator = AccountId::try_from([inputs[11], inputs[10]])
+            .map_err(|_| ZoroSwapError::NotAZoroSwap)?;
+        let pool = AccountId::try_from([inputs[13], inputs[12]])
+            .map_err(|_| ZoroSwapError::NotAZoroSwap)?;
+        let next_hop_recipient = Word::from([inputs[16], inputs[17], inputs[18], inputs[19]]);
+        let next_hop = if next_hop_recipient == Word::default() {
+            None
+        } else {
+            let next_pool = AccountId::try_from([inputs[15], inputs[14]])
+                .map_err(|_| ZoroSwapError::NotAZoroSwap)?;
+            let next_faucet = AccountId::try_from([inputs[23], inputs[22]])
+                .map_err(|_| ZoroSwapError::NotAZoroSwap)?;
+            let next_hop = RouteHop {
+                pool: next_pool,
+                faucet_id: next_faucet,
+                min_amount_out: inputs[20].as_int(),
+            };
+            Some((next_hop_recipient, next_hop))
+        };
+
+        Ok(Self {
+            min_amount_out: inputs[0].as_int(),
+            note,
+            creator,
+            pool,
+            requested_faucet,
+            deadline: deadline.into(),
+            payout,
+            next_hop,
+            aux: inputs[6],
+        })
+    }
+
+    /// Returns the ZoroSwap note.
+    pub fn note(&self) -> &Note {
+        &self.note
+    }
+
+    /// Returns the account that created the swap, is refunded and can cancel it.
+    pub fn creator(&self) -> AccountId {
+        self.creator
+    }
+
+    /// Returns the pool account allowed to execute the swap.
+    pub fn pool(&self) -> AccountId {
+        self.pool
+    }
+
+    /// Returns the asset offered by the creator.
+    pub fn offered_asset(&self) -> Asset {
+        *self.note.assets().iter().next().unwrap()
+    }
+
+    /// Returns the faucet of the requested asset.
+    pub fn requested_faucet(&self) -> AccountId {
+        self.requested_faucet
+    }
+
+    /// Returns the minimum output amount below which the offered asset is refunded.
+    pub fn min_amount_out(&self) -> u64 {
+        self.min_amount_out
+    }
+
+    /// Returns the last block in which the swap can be executed.
+    pub fn deadline(&self) -> BlockNumber {
+        self.deadline
+    }
+
+    /// Returns the account paid by the P2ID output of the swap, unused if the output goes to a
+    /// next hop.
+    pub fn payout(&self) -> AccountId {
+        self.payout
+    }
+
+    /// Returns the recipient of the note the output is paid into instead of a P2ID note, if any.
+    pub fn next_hop(&self) -> Option<Word> {
+        self.next_hop.map(|(recipient, _)| recipient)
+    }
+
+    /// Returns the next hop checked before executing the swap, if the output is paid into one.
+    pub fn next_hop_swap(&self) -> Option<RouteHop> {
+        self.next_hop.map(|(_, next_hop)| next_hop)
+    }
+
+    /// Returns the pool account of the next hop as a foreign account, with the pool states the
+    /// swap reads to quote the next hop, or `None` without next hop. The transaction consuming
+    /// the swap note needs it.
+    pub fn next_hop_foreign_account(&self) -> Result<Option<ForeignAccount>, ZoroSwapError> {
+        let Some((_, next_hop)) = self.next_hop else {
+            return Ok(None);
+        };
+        let keys = [
+            pool_state_key(self.requested_faucet),
+            pool_state_key(next_hop.faucet_id),
+        ];
+        let storage = AccountStorageRequirements::new([(POOL_STATE_SLOT, &keys)]);
+
+        Ok(Some(ForeignAccount::public(next_hop.pool, storage)?))
+    }
+
+    /// Returns the aux value of the P2ID outputs of the swap.
+    pub fn aux(&self) -> Felt {
+        self.aux
+    }
+
+    /// Returns the recipient of the P2ID note paying the payout account when the swap is
+    /// executed, unless the output goes to a next hop.
+    ///
+    /// The P2ID note uses the swap serial number with its last element incremented.
+    pub fn payout_recipient(&self) -> Result<NoteRecipient, ZoroSwapError> {
+        Ok(build_p2id_recipient(self.payout, self.p2id_serial_num())?)
+    }
+
+    /// Returns the recipient of the P2ID note refunding the offered asset to the creator.
+    ///
+    /// The refund uses the serial number of the payout, only one of them is ever created.
+    pub fn refund_recipient(&self) -> Result<NoteRecipient, ZoroSwapError> {
+        Ok(build_p2id_recipient(
+            self.creator(),
+            self.p2id_serial_num(),
+        )?)
+    }
+
+    /// Builds the request consuming the swap note for the pool account, reading the pool of the
+    /// next hop if any.
+    ///
+    /// The details of a next hop note are not known to the swap note; see
+    /// [`crate::zoro_route::ZoroRoute::first_hop_request`] to create it as a full note.
+    pub fn consume_request(&self, args: ZoroSwapArgs) -> Result<TransactionRequest, ZoroSwapError> {
+        let mut expected_recipients = vec![self.refund_recipient()?];
+        if self.next_hop.is_none() {
+            expected_recipients.push(self.payout_recipient()?);
+        }
+
+        Ok(TransactionRequestBuilder::new()
+            .unauthenticated_input_notes([(self.note.clone(), Some(args.to_word()))])
+            .expected_output_recipients(expected_recipients)
+            .foreign_accounts(self.next_hop_foreign_account()?)
+            .build()?)
+    }
+
+    /// Builds the request cancelling the swap, to be executed by the creator. The creator gets
+    /// the offered asset back and no P2ID note is created.
+    pub fn cancel_request(&self) -> Result<TransactionRequest, ZoroSwapError> {
+        Ok(TransactionRequestBuilder::new()
+            .unauthenticated_input_notes([(self.note.clone(), None)])
+            .build()?)
+    }
+
+    /// Reads the outcome of the swap from the payout or refund note created when consuming it.
+    pub fn decode_output(&self, output: &OutputNote) -> Result<ZoroSwapOutcome, ZoroSwapError> {
+        let assets = output.assets().ok_or(ZoroSwapError::NotASwapOutput)?;
+        if assets.num_assets() != 1 {
+            return Err(ZoroSwapError::NotASwapOutput);
+        }
+        let payout_recipient = match self.next_hop() {
+            Some(next_hop) => next_hop,
+            None => self.payout_recipient()?.digest(),
+        };
+        let payout_id = NoteId::new(payout_recipient, assets.commitment());
+        let refund_id = NoteId::new(self.refund_recipient()?.digest(), assets.commitment());
+
+        let asset = *assets.iter().next().unwrap();
+        match asset {
+            Asset::Fungible(payout)
+                if payout.faucet_id() == self.requested_faucet && output.id() == payout_id =>
+            {
+                Ok(ZoroSwapOutcome::Executed {
+                    amount_out: payout.amount(),
+                })
+            }
+            _ if asset == self.offered_asset() && output.id() == refund_id => {
+                Ok(ZoroSwapOutcome::Refunded(asset))
+            }
+            _ => Err(ZoroSwapError::NotASwapOutput),
+        }
+    }
+
+    // Serial number of the P2ID outputs, the swap serial number with its last element incremented
+    fn p2id_serial_num(&self) -> Word {
+        let serial_num = self.note.serial_num();
+        Word::from([
+            serial_num[0],
+            serial_num[1],
+            serial_num[2],
+            serial_num[3] + Felt::new(1),
+        ])
+    }
+}
+
+/// Returns the swap notes created by `creator` that the client knows to be unconsumed, i.e.
+/// the swaps `creator` can still cancel with [`ZoroSwapNote::cancel_request`].
+///
+/// The second hops of routes are sent by the pool account of the first hop, they are found if the
+/// client tracks that pool account.
+pub async fn find_outstanding_zoro_swaps<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &Client<AUTH>,
+    creator: AccountId,
+) -> Result<Vec<ZoroSwapNote>, ZoroSwapError> {
+    let mut swaps = Vec::new();
+    for filter in [NoteFilter::Expected, NoteFilter::Committed] {
+        for record in client.get_output_notes(filter).await? {
+            // only notes with full details can be consumed again
+            let Ok(note) = Note::try_from(record) else {
+                continue;
+            };
+            let Ok(swap) = ZoroSwapNote::from_note(note) else {
+                continue;
+            };
+            if swap.creator() == creator {
+                swaps.push(swap);
+            }
+        }
+    }
+
+    Ok(swaps)
+}
+
+/// Returns the recipient of a ZoroSwap note built with [`ZoroSwapNote::with_payout`] from these
+/// parameters, whatever its asset.
+///
+/// This is the recipient a previous hop pays into (see [`ZoroSwapNote::with_next_hop`]), as the
+/// amount of the note is only known once the previous hop is executed.
+#[allow(clippy::too_many_arguments)]
+pub fn zoro_swap_recipient(
+    creator: AccountId,
+    pool: AccountId,
+    requested_faucet: AccountId,
+    min_amount_out: u64,
+    deadline: BlockNumber,
+    payout: AccountId,
+    aux: Felt,
+    serial_num: Word,
+) -> Result<NoteRecipient, ZoroSwapError> {
+    let inputs = swap_inputs(
+        creator,
+        pool,
+        requested_faucet,
+        min_amount_out,
+        deadline,
+        payout,
+        NoteTag::from_account_id(payout),
+        aux,
+        None,
+    )?;
+    Ok(NoteRecipient::new(serial_num, zoro_swap_script(), inputs))
+}
+
+/// Returns the tag of ZoroSwap notes, under which pool operators find them.
+pub fn zoro_swap_tag() -> NoteTag {
+    NoteTag::for_public_use_case(0, 0, NoteExecutionMode::Local).unwrap()
+}
+
+/// Compiles the ZoroSwap note script, linking it to the pool account procedures and the P2ID
+/// note script of miden-lib.
+pub fn zoro_swap_script() -> NoteScript {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let path: PathBuf = [manifest_dir, "masm", "notes", "zoro_swap.masm"]
+        .iter()
+        .collect();
+
+    let note_code: String = fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("Error reading {}: {}", path.display(), err))
+        // the payout and refund notes are standard P2ID notes
+        .replace(
+            "{{P2ID_SCRIPT_ROOT}}",
+            &word_to_masm(WellKnownNote::P2ID.script_root()),
+        );
+
+    // the swap itself is executed by the pool account procedures
+    ScriptBuilder::new(true)
+        .with_dynamically_linked_library(&zoro_pool_library())
+        .unwrap()
+        .compile_note_script(note_code)
+        .unwrap()
+}
+
+// Note inputs of a ZoroSwap note, `next_hop` being the recipient and the hop the output is paid
+// into, or `None` for a P2ID payout
+#[allow(clippy::too_many_arguments)]
+fn swap_inputs(
+    creator: AccountId,
+    pool: AccountId,
+    requested_faucet: AccountId,
+    min_amount_out: u64,
+    deadline: BlockNumber,
+    payout: AccountId,
+    payout_tag: NoteTag,
+    aux: Felt,
+    next_hop: Option<(Word, RouteHop)>,
+) -> Result<NoteInputs, NoteError> {
+    // the P2ID refund is tagged for the creator, as standard P2ID notes are
+    let refund_tag = NoteTag::from_account_id(creator);
+    let (next_hop_recipient, [next_pool_suffix, next_pool_prefix], next_hop_asset) = match next_hop
+    {
+        Some((recipient, next_hop)) => (
+            recipient,
+            [next_hop.pool.suffix(), next_hop.pool.prefix().as_felt()],
+            [
+                Felt::new(next_hop.min_amount_out),
+                ZERO,
+                next_hop.faucet_id.suffix(),
+                next_hop.faucet_id.prefix().as_felt(),
+            ],
+        ),
+        None => (Word::default(), [ZERO; 2], [ZERO; 4]),
+    };
+    NoteInputs::new(vec![
+        // [min_amount_out, empty, out_asset_id_suffix, out_asset_id_prefix]
+        Felt::new(min_amount_out),
+        ZERO,
+        requested_faucet.suffix(),
+        requested_faucet.prefix().as_felt(),
+        // [deadline, payout_tag, p2id_aux, refund_tag]
+        Felt::from(deadline.as_u32()),
+        Felt::from(payout_tag),
+        aux,
+        Felt::from(refund_tag),
+        // [payout_id_suffix, payout_id_prefix, creator_id_suffix, creator_id_prefix]
+        payout.suffix(),
+        payout.prefix().as_felt(),
+        creator.suffix(),
+        creator.prefix().as_felt(),
+        // [pool_id_suffix, pool_id_prefix, next_pool_id_suffix, next_pool_id_prefix]
+        pool.suffix(),
+        pool.prefix().as_felt(),
+        next_pool_suffix,
+        next_pool_prefix,
+        // [NEXT_HOP_RECIPIENT]
+        next_hop_recipient[0],
+        next_hop_recipient[1],
+        next_hop_recipient[2],
+        next_hop_recipient[3],
+        // [next_min_amount_out, empty, next_out_asset_id_suffix, next_out_asset_id_prefix]
+        next_hop_asset[0],
+        next_hop_asset[1],
+        next_hop_asset[2],
+        next_hop_asset[3],
+    ])
+}
+
+// ZoroSwap notes are public and sent by their creator under the ZoroSwap tag
+fn swap_note(
+    creator: AccountId,
+    offered_asset: Asset,
+    inputs: NoteInputs,
+    serial_num: Word,
+) -> Result<Note, NoteError> {
+    let metadata = NoteMetadata::new(
+        creator,
+        NoteType::Public,
+        zoro_swap_tag(),
+        NoteExecutionHint::always(),
+        Felt::new(0),
+    )?;
+    let recipient = NoteRecipient::new(serial_num, zoro_swap_script(), inputs);
+    Ok(Note::new(
+        NoteAssets::new(vec![offered_asset])?,
+        metadata,
+        recipient,
+    ))
+}
@@ -0,0 +1,113 @@
+use masm_project_template::codec::{
+    CodecError, be_felts_to_bytes, be_limbs_to_bytes, bytes_to_be_felts, bytes_to_be_limbs,
+    bytes_to_le_limbs, digest_matches_be_felts, digest_matches_le_felts, felts_to_limbs,
+    le_felts_to_bytes, le_limbs_to_bytes, length_prefixed_be_felts,
+    length_prefixed_be_felts_to_bytes, length_prefixed_le_felts, limbs_to_felts, u256_to_words,
+    words_to_u256,
+};
+use masm_project_template::common::{keccak256_digest_inputs, sha256_digest_inputs};
+use miden_client::Felt;
+use proptest::prelude::*;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+proptest! {
+    #[test]
+    fn be_limbs_round_trip(bytes in proptest::collection::vec(any::<u8>(), 0..200)) {
+        let limbs = bytes_to_be_limbs(&bytes);
+        prop_assert_eq!(limbs.len(), bytes.len().div_ceil(4));
+        prop_assert_eq!(be_limbs_to_bytes(&limbs, bytes.len()).unwrap(), bytes);
+    }
+
+    #[test]
+    fn le_limbs_round_trip(bytes in proptest::collection::vec(any::<u8>(), 0..200)) {
+        let limbs = bytes_to_le_limbs(&bytes);
+        prop_assert_eq!(limbs.len(), bytes.len().div_ceil(4));
+        prop_assert_eq!(le_limbs_to_bytes(&limbs, bytes.len()).unwrap(), bytes);
+    }
+
+    #[test]
+    fn felts_round_trip(limbs in proptest::collection::vec(any::<u32>(), 0..64)) {
+        prop_assert_eq!(felts_to_limbs(&limbs_to_felts(&limbs)).unwrap(), limbs);
+    }
+
+    #[test]
+    fn length_prefixed_round_trip(bytes in proptest::collection::vec(any::<u8>(), 0..200)) {
+        let felts = length_prefixed_be_felts(&bytes);
+        prop_assert_eq!(felts[0], Felt::new(bytes.len() as u64));
+        prop_assert_eq!(length_prefixed_be_felts_to_bytes(&felts).unwrap(), bytes);
+    }
+
+    #[test]
+    fn length_prefixed_le_round_trip(bytes in proptest::collection::vec(any::<u8>(), 0..200)) {
+        let felts = length_prefixed_le_felts(&bytes);
+        prop_assert_eq!(felts[0], Felt::new(bytes.len() as u64));
+        prop_assert_eq!(le_felts_to_bytes(&felts[1..], bytes.len()).unwrap(), bytes);
+    }
+
+    #[test]
+    fn u256_words_round_trip(limbs in any::<[u32; 8]>()) {
+        let words = u256_to_words(&limbs);
+        // the most significant limb comes first
+        prop_assert_eq!(words[0][0], Felt::from(limbs[7]));
+        prop_assert_eq!(words[1][3], Felt::from(limbs[0]));
+        prop_assert_eq!(words_to_u256(&words).unwrap(), limbs);
+    }
+
+    #[test]
+    fn sha256_digest_packing(preimage in proptest::collection::vec(any::<u8>(), 0..200)) {
+        let digest = Sha256::digest(&preimage);
+        let inputs = sha256_digest_inputs(&preimage);
+
+        prop_assert!(digest_matches_be_felts(&digest, &inputs));
+        prop_assert_eq!(be_felts_to_bytes(&inputs, 32).unwrap(), digest.to_vec());
+        prop_assert_eq!(inputs, bytes_to_be_felts(&digest));
+    }
+
+    #[test]
+    fn keccak256_digest_packing(preimage in proptest::collection::vec(any::<u8>(), 0..200)) {
+        let digest = Keccak256::digest(&preimage);
+        let inputs = keccak256_digest_inputs(&preimage);
+
+        prop_assert!(digest_matches_le_felts(&digest, &inputs));
+        prop_assert_eq!(le_felts_to_bytes(&inputs, 32).unwrap(), digest.to_vec());
+    }
+
+    #[test]
+    fn digest_mismatch_detected(
+        preimage in proptest::collection::vec(any::<u8>(), 0..200),
+        flipped_byte in 0usize..32,
+    ) {
+        let mut digest = Sha256::digest(&preimage).to_vec();
+        let inputs = sha256_digest_inputs(&preimage);
+        digest[flipped_byte] ^= 1;
+
+        prop_assert!(!digest_matches_be_felts(&digest, &inputs));
+    }
+}
+
+#[test]
+fn be_packing_matches_sha256_words() {
+    // SHA-256("abc") = ba7816bf 8f01cfea 414140de 5dae2223 b00361a3 96177a9c b410ff61 f20015ad
+    let inputs = sha256_digest_inputs(b"abc");
+    let expected = limbs_to_felts(&[
+        0xba7816bf, 0x8f01cfea, 0x414140de, 0x5dae2223, 0xb00361a3, 0x96177a9c, 0xb410ff61,
+        0xf20015ad,
+    ]);
+    assert_eq!(inputs, expected);
+}
+
+#[test]
+fn invalid_limbs_rejected() {
+    assert_eq!(
+        felts_to_limbs(&[Felt::new(u32::MAX as u64 + 1)]),
+        Err(CodecError::NotU32(u32::MAX as u64 + 1))
+    );
+    assert_eq!(
+        be_limbs_to_bytes(&[0, 0], 9),
+        Err(CodecError::LengthMismatch {
+            limbs: 2,
+            byte_len: 9
+        })
+    );
+}
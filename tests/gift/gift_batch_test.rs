@@ -0,0 +1,114 @@
+use std::path::Path;
+use std::time::Duration;
+
+use masm_project_template::common::delete_keystore_and_store;
+use masm_project_template::constants::SYNC_STATE_WAIT_TIME;
+use masm_project_template::gift_batch::{
+    GIFT_BATCH_CSV_HEADER, create_gift_batch, write_gift_batch_csv,
+};
+use masm_project_template::{
+    common::{instantiate_client, setup_accounts_and_faucets},
+    constants::NETWORK_ID,
+};
+use miden_client::rpc::Endpoint;
+use miden_client::transaction::TransactionRequestBuilder;
+use miden_objects::account::NetworkId;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn create_gift_batch_success() -> Result<(), Box<dyn std::error::Error>> {
+    delete_keystore_and_store().await;
+
+    // -------------------------------------------------------------------------
+    // 1. Instantiate client
+    // -------------------------------------------------------------------------
+    let endpoint = if NETWORK_ID == NetworkId::Testnet {
+        Endpoint::testnet()
+    } else {
+        Endpoint::devnet()
+    };
+
+    let (mut client, keystore) = instantiate_client(endpoint).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let gift_amounts: Vec<u64> = (1..=50).collect();
+    let total: u64 = gift_amounts.iter().sum();
+
+    let balances = vec![
+        vec![total], // For account[0] => Alice
+        vec![0],     // For account[1] => Bob
+    ];
+    let (accounts, faucets) =
+        setup_accounts_and_faucets(&mut client, keystore, 2, 1, balances).await?;
+
+    // rename for clarity
+    let alice_account = accounts[0].clone();
+    let bob_account = accounts[1].clone();
+    let faucet = faucets[0].clone();
+
+    // -------------------------------------------------------------------------
+    // 2. Alice creates every gift in one transaction
+    // -------------------------------------------------------------------------
+    let entries =
+        create_gift_batch(&mut client, alice_account.id(), faucet.id(), &gift_amounts).await?;
+    assert_eq!(entries.len(), gift_amounts.len());
+
+    let csv_path = Path::new("./gift_batch.csv");
+    write_gift_batch_csv(csv_path, &entries, "https://qash.finance/gift")?;
+    let csv = std::fs::read_to_string(csv_path)?;
+    std::fs::remove_file(csv_path)?;
+
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some(GIFT_BATCH_CSV_HEADER));
+    let rows: Vec<&str> = lines.collect();
+    assert_eq!(rows.len(), entries.len());
+    for (line, entry) in rows.iter().zip(&entries) {
+        assert!(line.starts_with(&entry.note.id().to_hex()));
+        assert!(line.contains(&entry.secret.to_hex()));
+    }
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    let alice_account_state = client
+        .get_account(alice_account.id())
+        .await?
+        .expect("alice account not found");
+    let balance_alice = alice_account_state
+        .account()
+        .vault()
+        .get_balance(faucet.id())
+        .unwrap();
+    assert_eq!(balance_alice, 0);
+
+    // -------------------------------------------------------------------------
+    // 3. Bob opens one gift of the batch with its secret
+    // -------------------------------------------------------------------------
+    let entry = entries.last().unwrap().clone();
+    let consume_req = TransactionRequestBuilder::new()
+        .unauthenticated_input_notes([(entry.note, Some(entry.secret))])
+        .build()
+        .unwrap();
+    let tx_exec = client
+        .new_transaction(bob_account.id(), consume_req)
+        .await?;
+    client.submit_transaction(tx_exec).await?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await.unwrap();
+
+    let bob_account_state = client
+        .get_account(bob_account.id())
+        .await?
+        .expect("bob account not found");
+    let balance_bob = bob_account_state
+        .account()
+        .vault()
+        .get_balance(faucet.id())
+        .unwrap();
+    println!("balance_bob: {:?}", balance_bob);
+
+    assert_eq!(balance_bob, entry.amount);
+
+    Ok(())
+}
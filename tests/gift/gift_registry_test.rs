@@ -0,0 +1,162 @@
+use std::time::Duration;
+
+use masm_project_template::common::delete_keystore_and_store;
+use masm_project_template::constants::SYNC_STATE_WAIT_TIME;
+use masm_project_template::gift_registry::{GiftRegistry, GiftStatus};
+use masm_project_template::{
+    common::{
+        create_expiring_gift_note, create_gift_note_recallable, instantiate_client,
+        setup_accounts_and_faucets,
+    },
+    constants::NETWORK_ID,
+};
+use miden_client::note::NoteAssets;
+use miden_client::rpc::Endpoint;
+use miden_client::transaction::OutputNote;
+use miden_client::{
+    asset::{Asset, FungibleAsset},
+    transaction::TransactionRequestBuilder,
+};
+use miden_objects::account::NetworkId;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn gift_registry_tracks_claim_status() -> Result<(), Box<dyn std::error::Error>> {
+    delete_keystore_and_store().await;
+
+    // -------------------------------------------------------------------------
+    // 1. Instantiate client
+    // -------------------------------------------------------------------------
+    let endpoint = if NETWORK_ID == NetworkId::Testnet {
+        Endpoint::testnet()
+    } else {
+        Endpoint::devnet()
+    };
+
+    let (mut client, keystore) = instantiate_client(endpoint).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let balances = vec![
+        vec![300], // For account[0] => Alice
+        vec![0],   // For account[1] => Bob
+    ];
+    let (accounts, faucets) =
+        setup_accounts_and_faucets(&mut client, keystore, 2, 1, balances).await?;
+
+    // rename for clarity
+    let alice_account = accounts[0].clone();
+    let bob_account = accounts[1].clone();
+    let faucet = faucets[0].clone();
+
+    // -------------------------------------------------------------------------
+    // 2. Alice creates three gifts in one transaction and records them, the last two expire
+    //    right away
+    // -------------------------------------------------------------------------
+    let sync_height = client.get_sync_height().await?;
+
+    let mut gifts = Vec::new();
+    for i in 0..3 {
+        let rng = client.rng();
+        let serial_num = rng.inner_mut().draw_word();
+        let secret = rng.inner_mut().draw_word();
+        let assets = NoteAssets::new(vec![Asset::Fungible(
+            FungibleAsset::new(faucet.id(), 100).unwrap(),
+        )])?;
+        let gift_note = if i == 0 {
+            create_gift_note_recallable(
+                alice_account.id(),
+                assets,
+                secret.to_vec().try_into().unwrap(),
+                serial_num.to_vec().try_into().unwrap(),
+            )?
+        } else {
+            create_expiring_gift_note(
+                alice_account.id(),
+                assets,
+                secret.to_vec().try_into().unwrap(),
+                serial_num.to_vec().try_into().unwrap(),
+                sync_height,
+            )?
+        };
+        gifts.push((gift_note, secret));
+    }
+
+    let tx_request = TransactionRequestBuilder::new()
+        .own_output_notes(
+            gifts
+                .iter()
+                .map(|(note, _)| OutputNote::Full(note.clone()))
+                .collect(),
+        )
+        .build()
+        .unwrap();
+    let tx_exec = client
+        .new_transaction(alice_account.id(), tx_request)
+        .await?;
+    client.submit_transaction(tx_exec).await?;
+
+    let mut registry = GiftRegistry::load(&client).await?;
+    for (gift_note, _) in &gifts {
+        registry.record(&mut client, gift_note).await?;
+    }
+    // the expiry is read from the gift note
+    assert_eq!(registry.records()[0].expiry, None);
+    assert_eq!(registry.records()[1].expiry, Some(sync_height));
+
+    // recording the same gift twice is rejected
+    assert!(registry.record(&mut client, &gifts[0].0).await.is_err());
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+
+    let states = registry.resolve(&mut client).await?;
+    assert_eq!(states[0].status, GiftStatus::Pending);
+    assert_eq!(states[1].status, GiftStatus::Expired);
+    assert_eq!(states[2].status, GiftStatus::Expired);
+
+    // -------------------------------------------------------------------------
+    // 3. Bob claims the first gift, Alice reclaims the second one without the secret, and the
+    //    secret no longer opens the third one
+    // -------------------------------------------------------------------------
+    let consume_req = TransactionRequestBuilder::new()
+        .unauthenticated_input_notes([(gifts[0].0.clone(), Some(gifts[0].1))])
+        .build()
+        .unwrap();
+    let tx_exec = client
+        .new_transaction(bob_account.id(), consume_req)
+        .await?;
+    client.submit_transaction(tx_exec).await?;
+
+    let reclaim_req = TransactionRequestBuilder::new()
+        .unauthenticated_input_notes([(gifts[1].0.clone(), None)])
+        .build()
+        .unwrap();
+    let tx_exec = client
+        .new_transaction(alice_account.id(), reclaim_req)
+        .await?;
+    client.submit_transaction(tx_exec).await?;
+
+    let consume_req = TransactionRequestBuilder::new()
+        .unauthenticated_input_notes([(gifts[2].0.clone(), Some(gifts[2].1))])
+        .build()
+        .unwrap();
+    let expired_claim = client.new_transaction(bob_account.id(), consume_req).await;
+    assert!(expired_claim.is_err());
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+
+    // -------------------------------------------------------------------------
+    // 4. The registry reflects the new state after reloading from the store
+    // -------------------------------------------------------------------------
+    let registry = GiftRegistry::load(&client).await?;
+    assert_eq!(registry.records().len(), 3);
+
+    let summary = registry.summary(&mut client).await?;
+    println!("{summary}");
+
+    assert_eq!(summary.gifts[0].status, GiftStatus::Claimed);
+    assert_eq!(summary.gifts[1].status, GiftStatus::Reclaimed);
+    assert_eq!(summary.gifts[2].status, GiftStatus::Expired);
+    assert!(summary.gifts.iter().all(|gift| gift.committed));
+
+    Ok(())
+}
@@ -1,11 +1,14 @@
 use std::time::Duration;
 
-use masm_project_template::common::{delete_keystore_and_store, wait_for_notes};
+use masm_project_template::common::{
+    create_nft_faucet, delete_keystore_and_store, mint_non_fungible_asset, wait_for_notes,
+};
 use masm_project_template::constants::SYNC_STATE_WAIT_TIME;
 use masm_project_template::{
     common::{create_gift_note_recallable, instantiate_client, setup_accounts_and_faucets},
     constants::NETWORK_ID,
 };
+use miden_client::note::NoteAssets;
 use miden_client::rpc::Endpoint;
 use miden_client::transaction::OutputNote;
 use miden_client::{Felt, Word};
@@ -64,7 +67,9 @@ async fn create_and_open_gift_success() -> Result<(), Box<dyn std::error::Error>
     // alice create gift
     let gift_note = create_gift_note_recallable(
         alice_account.id(),
-        Asset::Fungible(FungibleAsset::new(faucet.id(), gift_amount).unwrap()),
+        NoteAssets::new(vec![Asset::Fungible(
+            FungibleAsset::new(faucet.id(), gift_amount).unwrap(),
+        )])?,
         secret,
         serial_num.to_vec().try_into().unwrap(),
     )?;
@@ -179,7 +184,10 @@ async fn open_gift_with_wrong_secret() {
     // alice create gift
     let gift_note = create_gift_note_recallable(
         alice_account.id(),
-        Asset::Fungible(FungibleAsset::new(faucet.id(), 100).unwrap()),
+        NoteAssets::new(vec![Asset::Fungible(
+            FungibleAsset::new(faucet.id(), 100).unwrap(),
+        )])
+        .unwrap(),
         secret.to_vec().try_into().unwrap(),
         serial_num.to_vec().try_into().unwrap(),
     )
@@ -219,3 +227,230 @@ async fn open_gift_with_wrong_secret() {
         .unwrap();
     client.submit_transaction(tx_exec).await.unwrap();
 }
+
+#[tokio::test]
+async fn create_and_open_multi_asset_gift_success() -> Result<(), Box<dyn std::error::Error>> {
+    delete_keystore_and_store().await;
+
+    // -------------------------------------------------------------------------
+    // 1. Instantiate client
+    // -------------------------------------------------------------------------
+    let endpoint = if NETWORK_ID == NetworkId::Testnet {
+        Endpoint::testnet()
+    } else {
+        Endpoint::devnet()
+    };
+
+    let (mut client, keystore) = instantiate_client(endpoint).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let gift_amounts = [60, 40];
+
+    let balances = vec![
+        vec![100, 100], // For account[0] => Alice
+        vec![0, 0],     // For account[1] => Bob
+    ];
+    let (accounts, faucets) =
+        setup_accounts_and_faucets(&mut client, keystore, 2, 2, balances).await?;
+
+    // rename for clarity
+    let alice_account = accounts[0].clone();
+    let bob_account = accounts[1].clone();
+
+    let rng = client.rng();
+    let serial_num = rng.inner_mut().draw_word();
+    let secret = rng.inner_mut().draw_word();
+
+    // alice create gift holding one asset from each faucet
+    let assets = NoteAssets::new(vec![
+        Asset::Fungible(FungibleAsset::new(faucets[0].id(), gift_amounts[0]).unwrap()),
+        Asset::Fungible(FungibleAsset::new(faucets[1].id(), gift_amounts[1]).unwrap()),
+    ])?;
+    let gift_note = create_gift_note_recallable(
+        alice_account.id(),
+        assets,
+        secret.to_vec().try_into().unwrap(),
+        serial_num.to_vec().try_into().unwrap(),
+    )?;
+
+    let tx_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(gift_note.clone())])
+        .build()
+        .unwrap();
+    let tx_exec = client
+        .new_transaction(alice_account.id(), tx_request)
+        .await?;
+    client.submit_transaction(tx_exec).await?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    // now bob open the gift
+    let consume_req = TransactionRequestBuilder::new()
+        .unauthenticated_input_notes([(gift_note, Some(secret))])
+        .build()
+        .unwrap();
+    let tx_exec = client
+        .new_transaction(bob_account.id(), consume_req)
+        .await?;
+    client.submit_transaction(tx_exec).await?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await.unwrap();
+
+    // bob should hold every asset of the gift
+    let bob_account_state = client
+        .get_account(bob_account.id())
+        .await?
+        .expect("bob account not found");
+
+    for (faucet, gift_amount) in faucets.iter().zip(gift_amounts) {
+        let balance_bob = bob_account_state
+            .account()
+            .vault()
+            .get_balance(faucet.id())
+            .unwrap();
+        println!("balance_bob ({}): {:?}", faucet.id().to_hex(), balance_bob);
+        assert_eq!(balance_bob, gift_amount);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_and_open_non_fungible_gift_success() -> Result<(), Box<dyn std::error::Error>> {
+    delete_keystore_and_store().await;
+
+    // -------------------------------------------------------------------------
+    // 1. Instantiate client
+    // -------------------------------------------------------------------------
+    let endpoint = if NETWORK_ID == NetworkId::Testnet {
+        Endpoint::testnet()
+    } else {
+        Endpoint::devnet()
+    };
+
+    let (mut client, keystore) = instantiate_client(endpoint).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let gift_amount = 100;
+
+    let balances = vec![
+        vec![100], // For account[0] => Alice
+        vec![0],   // For account[1] => Bob
+    ];
+    let (accounts, faucets) =
+        setup_accounts_and_faucets(&mut client, keystore.clone(), 2, 1, balances).await?;
+
+    // rename for clarity
+    let alice_account = accounts[0].clone();
+    let bob_account = accounts[1].clone();
+    let faucet = faucets[0].clone();
+
+    // -------------------------------------------------------------------------
+    // 2. Mint a non-fungible asset to alice
+    // -------------------------------------------------------------------------
+    let (nft_faucet, _) = create_nft_faucet(&mut client, keystore).await?;
+    client.sync_state().await?;
+
+    let (nft, nft_note) = mint_non_fungible_asset(
+        &mut client,
+        &nft_faucet,
+        alice_account.id(),
+        b"qash gift card #1".to_vec(),
+    )
+    .await?;
+
+    wait_for_notes(&mut client, &alice_account, 1).await?;
+
+    let consume_req = TransactionRequestBuilder::new()
+        .authenticated_input_notes([(nft_note.id(), None)])
+        .build()
+        .unwrap();
+    let tx_exec = client
+        .new_transaction(alice_account.id(), consume_req)
+        .await?;
+    client.submit_transaction(tx_exec).await?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    // -------------------------------------------------------------------------
+    // 3. Alice gifts the non-fungible asset together with fungible tokens
+    // -------------------------------------------------------------------------
+    let rng = client.rng();
+    let serial_num = rng.inner_mut().draw_word();
+    let secret = rng.inner_mut().draw_word();
+
+    let assets = NoteAssets::new(vec![
+        Asset::Fungible(FungibleAsset::new(faucet.id(), gift_amount).unwrap()),
+        Asset::NonFungible(nft),
+    ])?;
+    let gift_note = create_gift_note_recallable(
+        alice_account.id(),
+        assets,
+        secret.to_vec().try_into().unwrap(),
+        serial_num.to_vec().try_into().unwrap(),
+    )?;
+
+    let tx_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(gift_note.clone())])
+        .build()
+        .unwrap();
+    let tx_exec = client
+        .new_transaction(alice_account.id(), tx_request)
+        .await?;
+    client.submit_transaction(tx_exec).await?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    // -------------------------------------------------------------------------
+    // 4. Bob opens the gift
+    // -------------------------------------------------------------------------
+    let consume_req = TransactionRequestBuilder::new()
+        .unauthenticated_input_notes([(gift_note, Some(secret))])
+        .build()
+        .unwrap();
+    let tx_exec = client
+        .new_transaction(bob_account.id(), consume_req)
+        .await?;
+    client.submit_transaction(tx_exec).await?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await.unwrap();
+
+    let alice_account_state = client
+        .get_account(alice_account.id())
+        .await?
+        .expect("alice account not found");
+    let bob_account_state = client
+        .get_account(bob_account.id())
+        .await?
+        .expect("bob account not found");
+
+    let balance_bob = bob_account_state
+        .account()
+        .vault()
+        .get_balance(faucet.id())
+        .unwrap();
+    println!("balance_bob: {:?}", balance_bob);
+
+    assert_eq!(balance_bob, gift_amount);
+    assert!(
+        bob_account_state
+            .account()
+            .vault()
+            .has_non_fungible_asset(nft)
+            .unwrap()
+    );
+    assert!(
+        !alice_account_state
+            .account()
+            .vault()
+            .has_non_fungible_asset(nft)
+            .unwrap()
+    );
+
+    Ok(())
+}
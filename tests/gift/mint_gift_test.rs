@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use masm_project_template::common::{
+    create_auth_faucet, create_basic_account, create_no_auth_faucet, delete_keystore_and_store,
+    mint_gift_note,
+};
+use masm_project_template::constants::SYNC_STATE_WAIT_TIME;
+use masm_project_template::{common::instantiate_client, constants::NETWORK_ID};
+use miden_client::account::AccountStorageMode;
+use miden_client::rpc::Endpoint;
+use miden_client::transaction::TransactionRequestBuilder;
+use miden_objects::account::NetworkId;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn mint_and_open_gift_success() -> Result<(), Box<dyn std::error::Error>> {
+    delete_keystore_and_store().await;
+
+    // -------------------------------------------------------------------------
+    // 1. Instantiate client
+    // -------------------------------------------------------------------------
+    let endpoint = if NETWORK_ID == NetworkId::Testnet {
+        Endpoint::testnet()
+    } else {
+        Endpoint::devnet()
+    };
+
+    let (mut client, keystore) = instantiate_client(endpoint).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let gift_amount = 100;
+
+    // one faucet without auth, one faucet guarded by a falcon key
+    let no_auth_faucet = create_no_auth_faucet(
+        &mut client,
+        "QASH",
+        1_000_000,
+        8,
+        AccountStorageMode::Public,
+    )
+    .await?;
+    let (auth_faucet, _) = create_auth_faucet(
+        &mut client,
+        keystore.clone(),
+        "QASH",
+        1_000_000,
+        8,
+        AccountStorageMode::Public,
+    )
+    .await?;
+    let (bob_account, _) = create_basic_account(&mut client, keystore).await?;
+    client.sync_state().await?;
+
+    for faucet in [&no_auth_faucet, &auth_faucet] {
+        // -------------------------------------------------------------------------
+        // 2. Faucet mints straight into a gift note
+        // -------------------------------------------------------------------------
+        let secret = client.rng().inner_mut().draw_word();
+        let gift_note =
+            mint_gift_note(&mut client, faucet.id(), gift_amount, secret.into()).await?;
+        assert_eq!(gift_note.metadata().sender(), faucet.id());
+
+        sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+        client.sync_state().await?;
+
+        // -------------------------------------------------------------------------
+        // 3. Bob opens the gift
+        // -------------------------------------------------------------------------
+        let consume_req = TransactionRequestBuilder::new()
+            .unauthenticated_input_notes([(gift_note, Some(secret))])
+            .build()
+            .unwrap();
+        let tx_exec = client
+            .new_transaction(bob_account.id(), consume_req)
+            .await?;
+        client.submit_transaction(tx_exec).await?;
+
+        sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+        client.sync_state().await.unwrap();
+
+        let bob_account_state = client
+            .get_account(bob_account.id())
+            .await?
+            .expect("bob account not found");
+        let balance_bob = bob_account_state
+            .account()
+            .vault()
+            .get_balance(faucet.id())
+            .unwrap();
+        println!("balance_bob ({}): {:?}", faucet.id().to_hex(), balance_bob);
+
+        assert_eq!(balance_bob, gift_amount);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use std::path::Path;
+use std::time::Duration;
+
+use masm_project_template::common::{create_gift_note, delete_keystore_and_store};
+use masm_project_template::constants::SYNC_STATE_WAIT_TIME;
+use masm_project_template::private_gift::PrivateGift;
+use masm_project_template::{
+    common::{instantiate_client, setup_accounts_and_faucets},
+    constants::NETWORK_ID,
+};
+use miden_client::note::{NoteAssets, NoteType};
+use miden_client::rpc::Endpoint;
+use miden_client::transaction::OutputNote;
+use miden_client::{
+    asset::{Asset, FungibleAsset},
+    transaction::TransactionRequestBuilder,
+};
+use miden_objects::account::NetworkId;
+use rand::rng;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn create_and_open_private_gift_success() -> Result<(), Box<dyn std::error::Error>> {
+    delete_keystore_and_store().await;
+
+    // -------------------------------------------------------------------------
+    // 1. Instantiate client
+    // -------------------------------------------------------------------------
+    let endpoint = if NETWORK_ID == NetworkId::Testnet {
+        Endpoint::testnet()
+    } else {
+        Endpoint::devnet()
+    };
+
+    let (mut client, keystore) = instantiate_client(endpoint).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let gift_amount = 100;
+
+    let balances = vec![
+        vec![100], // For account[0] => Alice
+        vec![0],   // For account[1] => Bob
+    ];
+    let (accounts, faucets) =
+        setup_accounts_and_faucets(&mut client, keystore, 2, 1, balances).await?;
+
+    // rename for clarity
+    let alice_account = accounts[0].clone();
+    let bob_account = accounts[1].clone();
+    let faucet = faucets[0].clone();
+
+    let rng = client.rng();
+    let serial_num = rng.inner_mut().draw_word();
+    let secret = rng.inner_mut().draw_word();
+
+    // -------------------------------------------------------------------------
+    // 2. Alice creates a private gift, only its commitment goes on-chain
+    // -------------------------------------------------------------------------
+    let gift_note = create_gift_note(
+        alice_account.id(),
+        NoteAssets::new(vec![Asset::Fungible(
+            FungibleAsset::new(faucet.id(), gift_amount).unwrap(),
+        )])?,
+        secret.to_vec().try_into().unwrap(),
+        serial_num.to_vec().try_into().unwrap(),
+        NoteType::Private,
+    )?;
+
+    let after_block_num = client.get_sync_height().await?;
+
+    let tx_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(gift_note.clone())])
+        .build()
+        .unwrap();
+    let tx_exec = client
+        .new_transaction(alice_account.id(), tx_request)
+        .await?;
+    client.submit_transaction(tx_exec.clone()).await?;
+
+    // the note published by the transaction carries no details
+    let created_note = tx_exec.created_notes().get_note(0);
+    assert!(matches!(created_note, OutputNote::Header(_)));
+
+    // -------------------------------------------------------------------------
+    // 3. Seal the gift and hand it over off-chain
+    // -------------------------------------------------------------------------
+    let gift = PrivateGift::new(gift_note.clone(), secret, after_block_num)?;
+    let key = PrivateGift::generate_key(&mut rng());
+    let gift_path = Path::new("./private_gift.bin");
+    gift.write_sealed(gift_path, &key, &mut rng())?;
+
+    // a wrong key must not open the gift
+    let wrong_key = PrivateGift::generate_key(&mut rng());
+    assert!(PrivateGift::read_sealed(gift_path, &wrong_key).is_err());
+
+    let received_gift = PrivateGift::read_sealed(gift_path, &key)?;
+    std::fs::remove_file(gift_path)?;
+    assert_eq!(received_gift, gift);
+
+    // -------------------------------------------------------------------------
+    // 4. Bob imports the gift note and claims it
+    // -------------------------------------------------------------------------
+    let note_id = received_gift.import(&mut client).await?;
+    assert_eq!(note_id, gift_note.id());
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    let consume_req = TransactionRequestBuilder::new()
+        .authenticated_input_notes([(note_id, Some(received_gift.secret()))])
+        .build()
+        .unwrap();
+    let tx_exec = client
+        .new_transaction(bob_account.id(), consume_req)
+        .await?;
+    client.submit_transaction(tx_exec).await?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await.unwrap();
+
+    let bob_account_state = client
+        .get_account(bob_account.id())
+        .await?
+        .expect("bob account not found");
+    let balance_bob = bob_account_state
+        .account()
+        .vault()
+        .get_balance(faucet.id())
+        .unwrap();
+    println!("balance_bob: {:?}", balance_bob);
+
+    assert_eq!(balance_bob, gift_amount);
+
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use k256::ecdsa::SigningKey;
+
+use masm_project_template::common::{
+    delete_keystore_and_store, instantiate_client, setup_accounts_and_faucets, wait_for_notes,
+};
+use masm_project_template::constants::NETWORK_ID;
+use masm_project_template::ecdsa_auth::{
+    ecdsa_wallet_builder, new_ecdsa_transaction, tx_summary_prehash,
+};
+use miden_client::account::{Account, AccountStorageMode};
+use miden_client::asset::FungibleAsset;
+use miden_client::keystore::FilesystemKeyStore;
+use miden_client::note::{Note, NoteType};
+use miden_client::rpc::Endpoint;
+use miden_client::transaction::{OutputNote, TransactionRequestBuilder};
+use miden_client::{Felt, Word, ZERO};
+use miden_lib::note::create_p2id_note;
+use miden_objects::account::NetworkId;
+use rand::RngCore;
+use rand::rngs::StdRng;
+
+type Client = miden_client::Client<FilesystemKeyStore<StdRng>>;
+
+fn random_signing_key() -> SigningKey {
+    loop {
+        if let Ok(key) = SigningKey::from_bytes(&rand::random::<[u8; 32]>().into()) {
+            return key;
+        }
+    }
+}
+
+/// Creates Alice, a faucet and a wallet controlled by `owner`, and mints 100 tokens to the wallet.
+/// Returns the minted note, not consumed yet.
+async fn setup_ecdsa_wallet(
+    owner: &SigningKey,
+) -> Result<(Client, Account, Account, Account, Note), Box<dyn std::error::Error>> {
+    delete_keystore_and_store().await;
+
+    let endpoint = if NETWORK_ID == NetworkId::Testnet {
+        Endpoint::testnet()
+    } else {
+        Endpoint::devnet()
+    };
+    let (mut client, keystore) = instantiate_client(endpoint).await?;
+    client.sync_state().await?;
+
+    let (accounts, faucets) =
+        setup_accounts_and_faucets(&mut client, keystore, 1, 1, vec![vec![0]]).await?;
+    let alice = accounts[0].clone();
+    let faucet = faucets[0].clone();
+
+    let mut init_seed = [0u8; 32];
+    client.rng().fill_bytes(&mut init_seed);
+    let (wallet, seed) = ecdsa_wallet_builder(init_seed, owner.verifying_key())
+        .storage_mode(AccountStorageMode::Public)
+        .build()?;
+    client.add_account(&wallet, Some(seed), false).await?;
+    println!("ecdsa wallet: {:?}", wallet.id());
+
+    let asset = FungibleAsset::new(faucet.id(), 100)?;
+    let mint_request = TransactionRequestBuilder::new().build_mint_fungible_asset(
+        asset,
+        wallet.id(),
+        NoteType::Public,
+        client.rng(),
+    )?;
+    let tx_result = client.new_transaction(faucet.id(), mint_request).await?;
+    client.submit_transaction(tx_result.clone()).await?;
+    let minted_note = match tx_result.created_notes().get_note(0) {
+        OutputNote::Full(note) => note.clone(),
+        _ => panic!("Expected OutputNote::Full, got something else"),
+    };
+    wait_for_notes(&mut client, &wallet, 1).await?;
+
+    Ok((client, alice, faucet, wallet, minted_note))
+}
+
+#[test]
+fn tx_summary_prehash_layout() {
+    let commitment = Word::from([Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)]);
+    let prehash = tx_summary_prehash(commitment);
+
+    // c3 is the most significant 64 bits, c0 the least significant ones
+    let mut expected = [0u8; 32];
+    expected[7] = 4;
+    expected[15] = 3;
+    expected[23] = 2;
+    expected[31] = 1;
+    assert_eq!(prehash, expected);
+}
+
+#[tokio::test]
+async fn ecdsa_auth_wallet_success() -> Result<(), Box<dyn std::error::Error>> {
+    let owner = random_signing_key();
+    let (mut client, alice, faucet, wallet, minted_note) = setup_ecdsa_wallet(&owner).await?;
+
+    // the owner signs the consumption of the minted note
+    let consume_request = TransactionRequestBuilder::new()
+        .authenticated_input_notes([(minted_note.id(), None)])
+        .build()?;
+    let tx_result =
+        new_ecdsa_transaction(&mut client, wallet.id(), consume_request, &owner).await?;
+    client.submit_transaction(tx_result).await?;
+
+    // and a payment of 40 tokens to Alice
+    let payment = create_p2id_note(
+        wallet.id(),
+        alice.id(),
+        vec![FungibleAsset::new(faucet.id(), 40)?.into()],
+        NoteType::Public,
+        ZERO,
+        client.rng(),
+    )?;
+    let payment_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(payment)])
+        .build()?;
+    let tx_result =
+        new_ecdsa_transaction(&mut client, wallet.id(), payment_request, &owner).await?;
+    client.submit_transaction(tx_result).await?;
+    client.sync_state().await?;
+
+    let wallet_state = client.get_account(wallet.id()).await?.unwrap();
+    assert_eq!(wallet_state.account().vault().get_balance(faucet.id())?, 60);
+    assert_eq!(wallet_state.account().nonce(), Felt::new(2));
+
+    println!("ecdsa auth wallet success");
+    Ok(())
+}
+
+#[tokio::test]
+async fn ecdsa_auth_wrong_key() -> Result<(), Box<dyn std::error::Error>> {
+    let owner = random_signing_key();
+    let (mut client, alice, faucet, wallet, minted_note) = setup_ecdsa_wallet(&owner).await?;
+
+    // the owner signs the consumption of the minted note
+    let consume_request = TransactionRequestBuilder::new()
+        .authenticated_input_notes([(minted_note.id(), None)])
+        .build()?;
+    let tx_result = new_ecdsa_transaction(&mut client, wallet.id(), consume_request, &owner).await;
+    assert!(tx_result.is_ok());
+    client.submit_transaction(tx_result?).await?;
+
+    // but another key cannot sign a payment from the wallet
+    let payment = create_p2id_note(
+        wallet.id(),
+        alice.id(),
+        vec![FungibleAsset::new(faucet.id(), 40)?.into()],
+        NoteType::Public,
+        ZERO,
+        client.rng(),
+    )?;
+    let payment_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(payment)])
+        .build()?;
+    let tx_result = new_ecdsa_transaction(
+        &mut client,
+        wallet.id(),
+        payment_request,
+        &random_signing_key(),
+    )
+    .await;
+    assert!(tx_result.is_err());
+
+    Ok(())
+}
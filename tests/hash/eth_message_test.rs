@@ -0,0 +1,183 @@
+use miden_client::account::{AccountId, AccountStorageMode};
+use miden_client::rpc::Endpoint;
+use miden_client::{Felt, Word};
+use miden_objects::account::NetworkId;
+use miden_objects::testing::account_id::ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE;
+use miden_objects::vm::AdviceMap;
+use sha3::{Digest, Keccak256};
+
+use masm_project_template::codec::bytes_to_le_felts;
+use masm_project_template::common::{
+    build_and_submit_tx, create_evm_account, delete_keystore_and_store, instantiate_client,
+    prepare_felt_vec, prepare_script,
+};
+use masm_project_template::constants::{
+    ETH_MESSAGE_HASHES_SCRIPT_PATH, EVM_CODE_PATH, EVM_LIBRARY_PATH, NETWORK_ID,
+};
+use masm_project_template::ecdsa_auth::tx_summary_prehash;
+use masm_project_template::eth_message::{
+    Eip712Domain, MIDEN_ACTION_TYPE, MidenAction, eip712_hash, personal_sign_hash,
+};
+
+// Separator and type hash hardcoded in evm.masm
+const MIDEN_DOMAIN_SEPARATOR: &str =
+    "53f1f05d33cfbc512db65e36ad68dda4f1406a85718f1a8df95b9790933b5211";
+const MIDEN_ACTION_TYPE_HASH: &str =
+    "43877ae58e92a8bbd4872b587cccbbbcfcb2df3db8293474bb3c3da165542ff4";
+
+fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    Keccak256::digest(bytes).into()
+}
+
+fn test_action(nonce: u64) -> MidenAction {
+    MidenAction {
+        account_id: AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap(),
+        nonce: Felt::new(nonce),
+        tx_summary: Word::from([
+            Felt::new(0x0123456789abcdef),
+            Felt::new(2),
+            Felt::new(u32::MAX as u64),
+            Felt::new(0xffff_ffff_0000_0000),
+        ]),
+    }
+}
+
+#[test]
+fn personal_sign_hash_reference() {
+    // hashMessage("Hello World") of ethers.js
+    assert_eq!(
+        hex::encode(personal_sign_hash(b"Hello World")),
+        "a1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2"
+    );
+}
+
+#[test]
+fn eip712_mail_reference() {
+    // Mail example of the EIP-712 specification
+    let domain = Eip712Domain {
+        name: "Ether Mail".into(),
+        version: "1".into(),
+        chain_id: Some(1),
+        verifying_contract: Some([0xcc; 20]),
+    };
+    let domain_separator = domain.separator();
+    assert_eq!(
+        hex::encode(domain_separator),
+        "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+    );
+
+    let person = |name: &str, wallet: &str| {
+        let mut encoded = keccak256(b"Person(string name,address wallet)").to_vec();
+        encoded.extend(keccak256(name.as_bytes()));
+        encoded.extend([0u8; 12]);
+        encoded.extend(hex::decode(wallet).unwrap());
+        keccak256(&encoded)
+    };
+    let mut mail =
+        keccak256(b"Mail(Person from,Person to,string contents)Person(string name,address wallet)")
+            .to_vec();
+    mail.extend(person("Cow", "cd2a3d9f938e13cd947ec05abc7fe734df8dd826"));
+    mail.extend(person("Bob", "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"));
+    mail.extend(keccak256(b"Hello, Bob!"));
+    let struct_hash = keccak256(&mail);
+    assert_eq!(
+        hex::encode(struct_hash),
+        "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+    );
+
+    assert_eq!(
+        hex::encode(eip712_hash(&domain_separator, &struct_hash)),
+        "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+    );
+}
+
+#[test]
+fn miden_action_encoding() {
+    assert_eq!(
+        hex::encode(Eip712Domain::miden().separator()),
+        MIDEN_DOMAIN_SEPARATOR
+    );
+    assert_eq!(
+        hex::encode(keccak256(MIDEN_ACTION_TYPE.as_bytes())),
+        MIDEN_ACTION_TYPE_HASH
+    );
+
+    // the account id is a uint128 holding the prefix in its high half
+    let action = test_action(7);
+    let mut encoded = hex::decode(MIDEN_ACTION_TYPE_HASH).unwrap();
+    encoded.extend([0u8; 16]);
+    encoded.extend(u128::from(action.account_id).to_be_bytes());
+    encoded.extend([0u8; 24]);
+    encoded.extend(7u64.to_be_bytes());
+    encoded.extend(tx_summary_prehash(action.tx_summary));
+    assert_eq!(action.struct_hash(), keccak256(&encoded));
+
+    let mut signed = vec![0x19, 0x01];
+    signed.extend(hex::decode(MIDEN_DOMAIN_SEPARATOR).unwrap());
+    signed.extend(action.struct_hash());
+    assert_eq!(action.eip712_hash(), keccak256(&signed));
+
+    assert_ne!(action.eip712_hash(), test_action(8).eip712_hash());
+}
+
+#[tokio::test]
+async fn eth_message_hashes_on_chain() -> Result<(), Box<dyn std::error::Error>> {
+    delete_keystore_and_store().await;
+
+    let endpoint = if NETWORK_ID == NetworkId::Testnet {
+        Endpoint::testnet()
+    } else {
+        Endpoint::devnet()
+    };
+    let (mut client, _) = instantiate_client(endpoint).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let evm_account = create_evm_account(&mut client, AccountStorageMode::Public)
+        .await
+        .unwrap();
+    println!("evm account: {:?}", evm_account.id());
+
+    // the advice stack is read in reverse order
+    let reversed = |mut felts: Vec<Felt>| {
+        felts.reverse();
+        felts
+    };
+
+    let message: [u8; 32] = rand::random();
+    let action = test_action(rand::random::<u32>() as u64);
+    let account_id = action.account_id;
+
+    let mut advice_map = AdviceMap::default();
+    advice_map.insert(
+        prepare_felt_vec(0).into(),
+        reversed(bytes_to_le_felts(&message)),
+    );
+    advice_map.insert(
+        prepare_felt_vec(1).into(),
+        reversed(bytes_to_le_felts(&personal_sign_hash(&message))),
+    );
+    let mut action_inputs = vec![
+        account_id.suffix(),
+        account_id.prefix().as_felt(),
+        action.nonce,
+    ];
+    action_inputs.extend(action.tx_summary.iter().copied());
+    advice_map.insert(prepare_felt_vec(2).into(), action_inputs);
+    advice_map.insert(
+        prepare_felt_vec(3).into(),
+        reversed(bytes_to_le_felts(&action.eip712_hash())),
+    );
+
+    let tx_script = prepare_script(
+        ETH_MESSAGE_HASHES_SCRIPT_PATH,
+        EVM_CODE_PATH,
+        EVM_LIBRARY_PATH,
+    )
+    .unwrap();
+    build_and_submit_tx(tx_script, advice_map, &mut client, evm_account.id())
+        .await
+        .unwrap();
+
+    println!("eth message hashes match");
+    Ok(())
+}
@@ -0,0 +1,192 @@
+use std::time::Duration;
+
+use masm_project_template::common::delete_keystore_and_store;
+use masm_project_template::constants::SYNC_STATE_WAIT_TIME;
+use masm_project_template::htlc::HtlcNote;
+use masm_project_template::{
+    common::{instantiate_client, setup_accounts_and_faucets},
+    constants::NETWORK_ID,
+};
+use miden_client::account::Account;
+use miden_client::block::BlockNumber;
+use miden_client::keystore::FilesystemKeyStore;
+use miden_client::note::NoteAssets;
+use miden_client::rpc::Endpoint;
+use miden_client::transaction::OutputNote;
+use miden_client::{
+    asset::{Asset, FungibleAsset},
+    transaction::TransactionRequestBuilder,
+};
+use miden_objects::account::NetworkId;
+use rand::rngs::StdRng;
+use tokio::time::sleep;
+
+type Client = miden_client::Client<FilesystemKeyStore<StdRng>>;
+
+const HTLC_AMOUNT: u64 = 100;
+const PREIMAGE: &[u8] = b"qash atomic swap secret";
+
+/// Alice locks `HTLC_AMOUNT` tokens for Bob in an HTLC timing out `timeout_delta` blocks after the
+/// current sync height.
+async fn lock_htlc(
+    timeout_delta: u32,
+) -> Result<(Client, Account, Account, Account, HtlcNote), Box<dyn std::error::Error>> {
+    delete_keystore_and_store().await;
+
+    // -------------------------------------------------------------------------
+    // 1. Instantiate client
+    // -------------------------------------------------------------------------
+    let endpoint = if NETWORK_ID == NetworkId::Testnet {
+        Endpoint::testnet()
+    } else {
+        Endpoint::devnet()
+    };
+
+    let (mut client, keystore) = instantiate_client(endpoint).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let balances = vec![
+        vec![HTLC_AMOUNT], // For account[0] => Alice
+        vec![0],           // For account[1] => Bob
+    ];
+    let (accounts, faucets) =
+        setup_accounts_and_faucets(&mut client, keystore, 2, 1, balances).await?;
+
+    // rename for clarity
+    let alice_account = accounts[0].clone();
+    let bob_account = accounts[1].clone();
+    let faucet = faucets[0].clone();
+
+    // -------------------------------------------------------------------------
+    // 2. Alice locks the tokens for Bob
+    // -------------------------------------------------------------------------
+    let timeout = BlockNumber::from(client.get_sync_height().await?.as_u32() + timeout_delta);
+    let serial_num = client.rng().inner_mut().draw_word();
+
+    let htlc = HtlcNote::from_preimage(
+        alice_account.id(),
+        bob_account.id(),
+        NoteAssets::new(vec![Asset::Fungible(
+            FungibleAsset::new(faucet.id(), HTLC_AMOUNT).unwrap(),
+        )])?,
+        PREIMAGE,
+        timeout,
+        serial_num,
+    )?;
+
+    let tx_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(htlc.note().clone())])
+        .build()
+        .unwrap();
+    let tx_exec = client
+        .new_transaction(alice_account.id(), tx_request)
+        .await?;
+    client.submit_transaction(tx_exec).await?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    Ok((client, alice_account, bob_account, faucet, htlc))
+}
+
+#[tokio::test]
+async fn htlc_claim_and_reveal_success() -> Result<(), Box<dyn std::error::Error>> {
+    let (mut client, alice_account, bob_account, faucet, htlc) = lock_htlc(100).await?;
+
+    // the HTLC parameters can be read back from the note
+    let received = HtlcNote::from_note(htlc.note().clone())?;
+    assert_eq!(received, htlc);
+
+    // a wrong preimage is rejected before building the transaction
+    assert!(htlc.claim_request(b"not the secret").is_err());
+
+    // -------------------------------------------------------------------------
+    // 3. Bob claims the HTLC, revealing the preimage
+    // -------------------------------------------------------------------------
+    let claim_req = htlc.claim_request(PREIMAGE)?;
+    let tx_exec = client.new_transaction(bob_account.id(), claim_req).await?;
+    client.submit_transaction(tx_exec).await?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    let bob_account_state = client
+        .get_account(bob_account.id())
+        .await?
+        .expect("bob account not found");
+    let balance_bob = bob_account_state
+        .account()
+        .vault()
+        .get_balance(faucet.id())
+        .unwrap();
+    println!("balance_bob: {:?}", balance_bob);
+    assert_eq!(balance_bob, HTLC_AMOUNT);
+
+    // -------------------------------------------------------------------------
+    // 4. Alice learns the preimage from the reveal note
+    // -------------------------------------------------------------------------
+    let revealed = htlc
+        .find_revealed_preimage(&client)
+        .await?
+        .expect("reveal note not found");
+    println!(
+        "revealed preimage for {}: {}",
+        alice_account.id(),
+        String::from_utf8_lossy(&revealed)
+    );
+    assert_eq!(revealed, PREIMAGE);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn htlc_refund_after_timeout_success() -> Result<(), Box<dyn std::error::Error>> {
+    // the timeout is reached as soon as the HTLC is committed
+    let (mut client, alice_account, _, faucet, htlc) = lock_htlc(0).await?;
+
+    let refund_req = htlc.refund_request();
+    let tx_exec = client
+        .new_transaction(alice_account.id(), refund_req)
+        .await?;
+    client.submit_transaction(tx_exec).await?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    let alice_account_state = client
+        .get_account(alice_account.id())
+        .await?
+        .expect("alice account not found");
+    let balance_alice = alice_account_state
+        .account()
+        .vault()
+        .get_balance(faucet.id())
+        .unwrap();
+    println!("balance_alice: {:?}", balance_alice);
+    assert_eq!(balance_alice, HTLC_AMOUNT);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[should_panic]
+async fn htlc_claim_after_timeout() {
+    let (mut client, _, bob_account, _, htlc) = lock_htlc(0).await.unwrap();
+
+    let claim_req = htlc.claim_request(PREIMAGE).unwrap();
+    client
+        .new_transaction(bob_account.id(), claim_req)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[should_panic]
+async fn htlc_refund_before_timeout() {
+    let (mut client, alice_account, _, _, htlc) = lock_htlc(100).await.unwrap();
+
+    client
+        .new_transaction(alice_account.id(), htlc.refund_request())
+        .await
+        .unwrap();
+}
@@ -0,0 +1,136 @@
+use std::time::Duration;
+
+use masm_project_template::common::{
+    create_basic_account, create_keccak256_note_from_preimage, delete_keystore_and_store,
+    keccak256_digest_inputs, keccak256_preimage_advice, prepare_felt_vec,
+};
+use masm_project_template::constants::{KECCAK256_PREIMAGE_KEY_SLOT, SYNC_STATE_WAIT_TIME};
+use masm_project_template::{common::instantiate_client, constants::NETWORK_ID};
+use miden_client::Felt;
+use miden_client::rpc::Endpoint;
+use miden_client::transaction::OutputNote;
+use miden_client::transaction::TransactionRequestBuilder;
+use miden_objects::account::NetworkId;
+use tokio::time::sleep;
+
+/// Locks a Keccak-256 hash-lock note with `preimage`, then tries to consume it revealing
+/// `revealed`.
+async fn lock_and_unlock(
+    preimage: &[u8],
+    revealed: &[u8],
+) -> Result<(), Box<dyn std::error::Error>> {
+    delete_keystore_and_store().await;
+
+    // -------------------------------------------------------------------------
+    // 1. Instantiate client
+    // -------------------------------------------------------------------------
+    let endpoint = if NETWORK_ID == NetworkId::Testnet {
+        Endpoint::testnet()
+    } else {
+        Endpoint::devnet()
+    };
+
+    let (mut client, keystore) = instantiate_client(endpoint).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let (account, _) = create_basic_account(&mut client, keystore).await?;
+    let rng = client.rng();
+    let serial_num = rng.inner_mut().draw_word();
+
+    // -------------------------------------------------------------------------
+    // 2. Create the hash-lock note
+    // -------------------------------------------------------------------------
+    let (keccak_note, _) =
+        create_keccak256_note_from_preimage(account.id(), preimage, serial_num.into())?;
+
+    let tx_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(keccak_note.clone())])
+        .build()
+        .unwrap();
+    let tx_exec = client.new_transaction(account.id(), tx_request).await?;
+    client.submit_transaction(tx_exec).await?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    // -------------------------------------------------------------------------
+    // 3. Consume the note revealing the preimage through the advice map
+    // -------------------------------------------------------------------------
+    let consume_req = TransactionRequestBuilder::new()
+        .unauthenticated_input_notes([(keccak_note, None)])
+        .extend_advice_map(keccak256_preimage_advice(revealed))
+        .build()
+        .unwrap();
+
+    let tx_exec = client.new_transaction(account.id(), consume_req).await?;
+    client.submit_transaction(tx_exec).await?;
+
+    Ok(())
+}
+
+#[test]
+fn keccak256_word_packing() {
+    // keccak256("") = 0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470
+    let inputs = keccak256_digest_inputs(&[]);
+    let expected: Vec<Felt> = [
+        0x0146d2c5u32,
+        0x3c23f786,
+        0xb27d7e92,
+        0xc003c7dc,
+        0x53b600e5,
+        0x3b2782ca,
+        0x04d8fa7b,
+        0x70a4855d,
+    ]
+    .into_iter()
+    .map(|word| Felt::new(word as u64))
+    .collect();
+    assert_eq!(inputs, expected);
+
+    // the preimage is length-prefixed and packed the same way
+    let preimage: [u8; 32] = core::array::from_fn(|i| i as u8 + 1);
+    let advice = keccak256_preimage_advice(&preimage);
+    let value = advice
+        .get(&prepare_felt_vec(KECCAK256_PREIMAGE_KEY_SLOT as u64).into())
+        .unwrap();
+    assert_eq!(value.len(), 9);
+    assert_eq!(value[0], Felt::new(32));
+    assert_eq!(value[1], Felt::new(0x04030201));
+    assert_eq!(value[8], Felt::new(0x201f1e1d));
+
+    // the last word of a preimage of any other length is right-padded with zero bytes
+    let advice = keccak256_preimage_advice(b"miden");
+    let value = advice
+        .get(&prepare_felt_vec(KECCAK256_PREIMAGE_KEY_SLOT as u64).into())
+        .unwrap();
+    assert_eq!(
+        value.as_slice(),
+        [Felt::new(5), Felt::new(0x6564696d), Felt::new(0x6e)]
+    );
+}
+
+#[tokio::test]
+async fn keccak256_test() -> Result<(), Box<dyn std::error::Error>> {
+    // a bytes32 secret as used by EVM HTLC contracts
+    let preimage: [u8; 32] = core::array::from_fn(|i| (i * 7 + 3) as u8);
+
+    lock_and_unlock(&preimage, &preimage).await
+}
+
+#[tokio::test]
+async fn keccak256_any_length_test() -> Result<(), Box<dyn std::error::Error>> {
+    // spans several keccak blocks and ends in a partial word
+    let preimage: Vec<u8> = (0..203u32).map(|i| (i * 13 + 5) as u8).collect();
+
+    lock_and_unlock(&preimage, &preimage).await
+}
+
+#[tokio::test]
+#[should_panic]
+async fn keccak256_wrong_preimage() {
+    let preimage = [42u8; 32];
+    let mut revealed = preimage;
+    revealed[31] ^= 1;
+
+    lock_and_unlock(&preimage, &revealed).await.unwrap();
+}
@@ -1,212 +1,165 @@
-use std::time::Duration;
+use k256::ecdsa::signature::hazmat::PrehashSigner;
+use k256::ecdsa::{Signature, SigningKey, VerifyingKey};
+use k256::{FieldBytes, FieldElement};
+use sha3::{Digest, Keccak256};
 
+use masm_project_template::codec::le_limbs_to_bytes;
 use masm_project_template::common::{
-    build_and_submit_tx, create_evm_account, delete_keystore_and_store, prepare_felt_vec,
-    prepare_script, wait_for_notes,
+    build_and_submit_tx, create_evm_account, delete_keystore_and_store, prepare_script,
 };
-use masm_project_template::constants::{
-    EVM_CODE_PATH, EVM_LIBRARY_PATH, LIBRARY_PATH, SYNC_STATE_WAIT_TIME, VERIFY_ECDSA_SCRIPT_PATH,
-};
-use masm_project_template::{
-    common::{create_gift_note_recallable, instantiate_client, setup_accounts_and_faucets},
-    constants::NETWORK_ID,
-};
-use miden_client::Felt;
+use masm_project_template::constants::{EVM_CODE_PATH, EVM_LIBRARY_PATH, VERIFY_ECDSA_SCRIPT_PATH};
+use masm_project_template::ecdsa::EcdsaAdviceInputs;
+use masm_project_template::{common::instantiate_client, constants::NETWORK_ID};
 use miden_client::account::AccountStorageMode;
 use miden_client::rpc::Endpoint;
-use miden_client::transaction::OutputNote;
-use miden_client::{
-    asset::{Asset, FungibleAsset},
-    keystore::FilesystemKeyStore,
-    transaction::TransactionRequestBuilder,
-};
 use miden_objects::account::NetworkId;
-use miden_objects::vm::AdviceMap;
-use tokio::time::sleep;
 
-struct Point {
-    x: Vec<u64>,
-    y: Vec<u64>,
-    z: Vec<u64>,
-}
+// Signature of a fixed message, in Montgomery form, with the public key in projective
+// coordinates
+const VECTOR: EcdsaAdviceInputs = EcdsaAdviceInputs {
+    public_key_x: [
+        1187647059, 1135132293, 1524607722, 3257770169, 1812770566, 4163599075, 3343690625,
+        2983146250,
+    ],
+    public_key_y: [
+        694970425, 3961647168, 2962892522, 3871680339, 479244527, 2106589630, 3531004100, 487738481,
+    ],
+    public_key_z: [
+        1718928786, 2222219308, 1537333708, 969814285, 1600645591, 2744076726, 1359599981,
+        1095895041,
+    ],
+    message_hash: [
+        1915140291, 1682821516, 1088031394, 2866424576, 2852209138, 1159876682, 234168247,
+        3360002988,
+    ],
+    signature_r: [
+        1494159694, 3668493121, 2315165624, 353127114, 974571799, 2051320959, 3421809437,
+        3258836281,
+    ],
+    signature_s: [
+        1259054195, 60155476, 2236955964, 2106542718, 1332177784, 1407189293, 11489664, 3695133146,
+    ],
+};
 
-impl Point {
-    fn new(x: Vec<u64>, y: Vec<u64>, z: Vec<u64>) -> Self {
-        Self { x, y, z }
-    }
-}
+// The same signature as k256 values
+const VECTOR_PUBLIC_KEY: &str = "04833717e7c244234b27df4dd32a6154e20b4f87f47077690dd97036ef13639c8adb3ea8c0ad5cf7703b1c1e06c5bfd7d8715bf5b050a679f86df4d6a40f7cf6e2";
+const VECTOR_PREHASH: &str = "901758698c0979c048d3b890dc1902a12f95a2ae049c632d180893631b815bb8";
+const VECTOR_R: &str = "80ddf241a5a335cd478edab64bd7c6853d3d8ff9350092242e1b1dbbb83379fa";
+const VECTOR_S: &str = "babac8f7b59351da58a314b02c2b2d435b82c25fc2ab3deae9b83d2bbc0cce50";
 
-struct Signature {
-    r: Vec<u64>,
-    s: Vec<u64>,
+fn limbs_to_field(limbs: &[u32; 8]) -> FieldElement {
+    let mut bytes = le_limbs_to_bytes(limbs, 32).unwrap();
+    bytes.reverse();
+    FieldElement::from_bytes(FieldBytes::from_slice(&bytes)).unwrap()
 }
 
-impl Signature {
-    fn new(r: Vec<u64>, s: Vec<u64>) -> Self {
-        Self { r, s }
-    }
+fn random_signature(message: &[u8]) -> EcdsaAdviceInputs {
+    let signing_key = loop {
+        if let Ok(key) = SigningKey::from_bytes(&rand::random::<[u8; 32]>().into()) {
+            break key;
+        }
+    };
+    let prehash: [u8; 32] = Keccak256::digest(message).into();
+    let signature: Signature = signing_key.sign_prehash(&prehash).unwrap();
+
+    EcdsaAdviceInputs::from_k256(signing_key.verifying_key(), &signature, &prehash)
 }
 
-#[tokio::test]
-async fn verify_ecdsa_success() -> Result<(), Box<dyn std::error::Error>> {
+async fn verify_on_chain(signatures: &[EcdsaAdviceInputs]) {
     delete_keystore_and_store().await;
 
-    // -------------------------------------------------------------------------
-    // 1. Instantiate client
-    // -------------------------------------------------------------------------
     let endpoint = if NETWORK_ID == NetworkId::Testnet {
         Endpoint::testnet()
     } else {
         Endpoint::devnet()
     };
 
-    let (mut client, keystore) = instantiate_client(endpoint).await.unwrap();
+    let (mut client, _) = instantiate_client(endpoint).await.unwrap();
     client.sync_state().await.unwrap();
 
     // create evm account
     let evm_account = create_evm_account(&mut client, AccountStorageMode::Public)
         .await
         .unwrap();
-
-    // print address
     println!("evm account: {:?}", evm_account.id());
 
-    // prepare script
     let tx_script =
         prepare_script(VERIFY_ECDSA_SCRIPT_PATH, EVM_CODE_PATH, EVM_LIBRARY_PATH).unwrap();
 
-    let public_key = Point::new(
-        vec![
-            1187647059, 1135132293, 1524607722, 3257770169, 1812770566, 4163599075, 3343690625,
-            2983146250,
-        ],
-        vec![
-            694970425, 3961647168, 2962892522, 3871680339, 479244527, 2106589630, 3531004100,
-            487738481,
-        ],
-        vec![
-            1718928786, 2222219308, 1537333708, 969814285, 1600645591, 2744076726, 1359599981,
-            1095895041,
-        ],
+    for signature in signatures {
+        build_and_submit_tx(
+            tx_script.clone(),
+            signature.to_advice_map(),
+            &mut client,
+            evm_account.id(),
+        )
+        .await
+        .unwrap();
+    }
+}
+
+#[test]
+fn ecdsa_advice_inputs_from_k256_vector() {
+    let verifying_key =
+        VerifyingKey::from_sec1_bytes(&hex::decode(VECTOR_PUBLIC_KEY).unwrap()).unwrap();
+    let r: [u8; 32] = hex::decode(VECTOR_R).unwrap().try_into().unwrap();
+    let s: [u8; 32] = hex::decode(VECTOR_S).unwrap().try_into().unwrap();
+    let signature = Signature::from_scalars(r, s).unwrap();
+    let prehash: [u8; 32] = hex::decode(VECTOR_PREHASH).unwrap().try_into().unwrap();
+
+    let inputs = EcdsaAdviceInputs::from_k256(&verifying_key, &signature, &prehash);
+
+    assert_eq!(inputs.message_hash, VECTOR.message_hash);
+    assert_eq!(inputs.signature_r, VECTOR.signature_r);
+    assert_eq!(inputs.signature_s, VECTOR.signature_s);
+
+    // both public keys are the same point: X1 * Z2 == X2 * Z1 and Y1 * Z2 == Y2 * Z1
+    let cross = |a: &[u32; 8], b: &[u32; 8]| {
+        (limbs_to_field(a) * limbs_to_field(b))
+            .normalize()
+            .to_bytes()
+    };
+    assert_eq!(
+        cross(&inputs.public_key_x, &VECTOR.public_key_z),
+        cross(&VECTOR.public_key_x, &inputs.public_key_z)
     );
-    let message_hash: Vec<u64> = vec![
-        1915140291, 1682821516, 1088031394, 2866424576, 2852209138, 1159876682, 234168247,
-        3360002988,
-    ];
-
-    let signature = Signature::new(
-        vec![
-            1494159694, 3668493121, 2315165624, 353127114, 974571799, 2051320959, 3421809437,
-            3258836281,
-        ],
-        vec![
-            1259054195, 60155476, 2236955964, 2106542718, 1332177784, 1407189293, 11489664,
-            3695133146,
-        ],
+    assert_eq!(
+        cross(&inputs.public_key_y, &VECTOR.public_key_z),
+        cross(&VECTOR.public_key_y, &inputs.public_key_z)
     );
+}
 
-    let mut advice_map = AdviceMap::default();
+#[tokio::test]
+async fn verify_ecdsa_success() -> Result<(), Box<dyn std::error::Error>> {
+    verify_on_chain(&[VECTOR]).await;
 
-    // -------------------------------------------------------------------------
-    // Signature - S
-    // -------------------------------------------------------------------------
-    let signature_s_first_half: Vec<Felt> = signature.s[..4]
-        .iter()
-        .map(|&x| Felt::new(x))
-        .rev()
-        .collect();
-    let signature_s_second_half: Vec<Felt> = signature.s[4..]
-        .iter()
-        .map(|&x| Felt::new(x))
-        .rev()
-        .collect();
-    advice_map.insert(prepare_felt_vec(0 as u64).into(), signature_s_second_half);
-    advice_map.insert(prepare_felt_vec(1 as u64).into(), signature_s_first_half);
-
-    // -------------------------------------------------------------------------
-    // Signature - R
-    // -------------------------------------------------------------------------
-    let signature_r_first_half: Vec<Felt> = signature.r[..4]
-        .iter()
-        .map(|&x| Felt::new(x))
-        .rev()
-        .collect();
-    let signature_r_second_half: Vec<Felt> = signature.r[4..]
-        .iter()
-        .map(|&x| Felt::new(x))
-        .rev()
-        .collect();
-    advice_map.insert(prepare_felt_vec(2 as u64).into(), signature_r_second_half);
-    advice_map.insert(prepare_felt_vec(3 as u64).into(), signature_r_first_half);
-
-    // -------------------------------------------------------------------------
-    // Message - Hash
-    // -------------------------------------------------------------------------
-    let message_hash_first_half: Vec<Felt> = message_hash[..4]
-        .iter()
-        .map(|&x| Felt::new(x))
-        .rev()
-        .collect();
-    let message_hash_second_half: Vec<Felt> = message_hash[4..]
-        .iter()
-        .map(|&x| Felt::new(x))
-        .rev()
-        .collect();
-    advice_map.insert(prepare_felt_vec(4 as u64).into(), message_hash_second_half);
-    advice_map.insert(prepare_felt_vec(5 as u64).into(), message_hash_first_half);
-
-    // -------------------------------------------------------------------------
-    // Public Key Z
-    // -------------------------------------------------------------------------
-    let public_key_z_first_half: Vec<Felt> = public_key.z[..4]
-        .iter()
-        .map(|&x| Felt::new(x))
-        .rev()
-        .collect();
-    let public_key_z_second_half: Vec<Felt> = public_key.z[4..]
-        .iter()
-        .map(|&x| Felt::new(x))
-        .rev()
-        .collect();
-    advice_map.insert(prepare_felt_vec(6 as u64).into(), public_key_z_second_half);
-    advice_map.insert(prepare_felt_vec(7 as u64).into(), public_key_z_first_half);
-
-    // -------------------------------------------------------------------------
-    // Public Key Y
-    // -------------------------------------------------------------------------
-    let public_key_y_first_half: Vec<Felt> = public_key.y[..4]
-        .iter()
-        .map(|&x| Felt::new(x))
-        .rev()
-        .collect();
-    let public_key_y_second_half: Vec<Felt> = public_key.y[4..]
-        .iter()
-        .map(|&x| Felt::new(x))
-        .rev()
-        .collect();
-    advice_map.insert(prepare_felt_vec(8 as u64).into(), public_key_y_second_half);
-    advice_map.insert(prepare_felt_vec(9 as u64).into(), public_key_y_first_half);
-
-    // -------------------------------------------------------------------------
-    // Public Key X
-    // -------------------------------------------------------------------------
-    let public_key_x_first_half: Vec<Felt> = public_key.x[..4]
-        .iter()
-        .map(|&x| Felt::new(x))
-        .rev()
-        .collect();
-    let public_key_x_second_half: Vec<Felt> = public_key.x[4..]
-        .iter()
-        .map(|&x| Felt::new(x))
-        .rev()
+    println!("verify ecdsa success");
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_ecdsa_k256_success() -> Result<(), Box<dyn std::error::Error>> {
+    // sign random messages of random lengths with random keys
+    let signatures: Vec<EcdsaAdviceInputs> = (0..3)
+        .map(|_| {
+            let message: Vec<u8> = (0..rand::random_range(1..200))
+                .map(|_| rand::random())
+                .collect();
+            random_signature(&message)
+        })
         .collect();
-    advice_map.insert(prepare_felt_vec(10 as u64).into(), public_key_x_second_half);
-    advice_map.insert(prepare_felt_vec(11 as u64).into(), public_key_x_first_half);
 
-    build_and_submit_tx(tx_script, advice_map, &mut client, evm_account.id())
-        .await
-        .unwrap();
+    verify_on_chain(&signatures).await;
 
-    println!("verify ecdsa success");
+    println!("verify ecdsa k256 success");
     Ok(())
 }
+
+#[tokio::test]
+#[should_panic]
+async fn verify_ecdsa_wrong_message() {
+    let mut signature = random_signature(b"transfer 100 tokens");
+    signature.message_hash = random_signature(b"transfer 1000 tokens").message_hash;
+
+    verify_on_chain(&[signature]).await;
+}
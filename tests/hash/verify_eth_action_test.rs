@@ -0,0 +1,160 @@
+use k256::ecdsa::SigningKey;
+use miden_client::account::{AccountId, AccountStorageMode, StorageSlot};
+use miden_client::keystore::FilesystemKeyStore;
+use miden_client::rpc::Endpoint;
+use miden_client::{ClientError, Felt, Word, ZERO};
+use miden_objects::account::NetworkId;
+use miden_objects::vm::AdviceMap;
+use rand::rngs::StdRng;
+
+use masm_project_template::common::{
+    build_and_submit_tx, create_evm_account_with_address, delete_keystore_and_store,
+    instantiate_client, prepare_script,
+};
+use masm_project_template::constants::{
+    ETH_ACTION_NONCE_SLOT, EVM_CODE_PATH, EVM_LIBRARY_PATH, NETWORK_ID,
+    VERIFY_ETH_ACTION_SCRIPT_PATH,
+};
+use masm_project_template::ecdsa::{eth_address, eth_address_storage_slots};
+use masm_project_template::eth_message::{MidenAction, eth_action_advice, get_eth_action_nonce};
+
+type Client = miden_client::Client<FilesystemKeyStore<StdRng>>;
+
+fn random_signing_key() -> SigningKey {
+    loop {
+        if let Ok(key) = SigningKey::from_bytes(&rand::random::<[u8; 32]>().into()) {
+            return key;
+        }
+    }
+}
+
+/// Signs `prehash` as an Ethereum wallet does, returning `r || s || v` with `v` in 27..=28.
+fn eth_sign(signing_key: &SigningKey, prehash: &[u8; 32]) -> [u8; 65] {
+    let (signature, recovery_id) = signing_key.sign_prehash_recoverable(prehash).unwrap();
+    let mut eth_signature = [0u8; 65];
+    eth_signature[..64].copy_from_slice(&signature.to_bytes());
+    eth_signature[64] = recovery_id.to_byte() + 27;
+    eth_signature
+}
+
+fn random_message() -> Word {
+    Word::from(rand::random::<[u32; 4]>().map(Felt::from))
+}
+
+/// Returns the advice map of `action` signed by `signer`.
+fn sign_action(action: &MidenAction, signer: &SigningKey) -> AdviceMap {
+    eth_action_advice(action, &eth_sign(signer, &action.eip712_hash())).unwrap()
+}
+
+/// Has the EVM account `account_id` verify the signed action of `advice_map`. Returns the result
+/// of the verifying transaction.
+async fn submit_action(
+    client: &mut Client,
+    account_id: AccountId,
+    advice_map: AdviceMap,
+) -> Result<(), ClientError> {
+    let tx_script = prepare_script(
+        VERIFY_ETH_ACTION_SCRIPT_PATH,
+        EVM_CODE_PATH,
+        EVM_LIBRARY_PATH,
+    )
+    .unwrap();
+    build_and_submit_tx(tx_script, advice_map, client, account_id).await
+}
+
+#[test]
+fn eth_action_nonce_starts_at_zero() {
+    let slots = eth_address_storage_slots(&[0xab; 20]);
+    assert_eq!(slots.len(), ETH_ACTION_NONCE_SLOT as usize + 1);
+    assert_eq!(
+        slots[ETH_ACTION_NONCE_SLOT as usize],
+        StorageSlot::Value(Word::from([ZERO; 4]))
+    );
+}
+
+#[tokio::test]
+async fn verify_eth_action_success() -> Result<(), Box<dyn std::error::Error>> {
+    delete_keystore_and_store().await;
+
+    let endpoint = if NETWORK_ID == NetworkId::Testnet {
+        Endpoint::testnet()
+    } else {
+        Endpoint::devnet()
+    };
+    let (mut client, _) = instantiate_client(endpoint).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let owner = random_signing_key();
+    let evm_account = create_evm_account_with_address(
+        &mut client,
+        AccountStorageMode::Public,
+        &eth_address(owner.verifying_key()),
+    )
+    .await
+    .unwrap();
+    println!("evm account: {:?}", evm_account.id());
+
+    // each action commits to the current nonce, which is consumed by its verification
+    for expected_nonce in 0..2u64 {
+        let nonce = get_eth_action_nonce(&client, evm_account.id()).await?;
+        assert_eq!(nonce, Felt::new(expected_nonce));
+
+        let action = MidenAction {
+            account_id: evm_account.id(),
+            nonce,
+            tx_summary: random_message(),
+        };
+        submit_action(&mut client, evm_account.id(), sign_action(&action, &owner)).await?;
+    }
+    assert_eq!(
+        get_eth_action_nonce(&client, evm_account.id()).await?,
+        Felt::new(2)
+    );
+
+    println!("verify eth action success");
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_eth_action_replay() -> Result<(), Box<dyn std::error::Error>> {
+    delete_keystore_and_store().await;
+
+    let endpoint = if NETWORK_ID == NetworkId::Testnet {
+        Endpoint::testnet()
+    } else {
+        Endpoint::devnet()
+    };
+    let (mut client, _) = instantiate_client(endpoint).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let owner = random_signing_key();
+    let evm_account = create_evm_account_with_address(
+        &mut client,
+        AccountStorageMode::Public,
+        &eth_address(owner.verifying_key()),
+    )
+    .await?;
+
+    let action = MidenAction {
+        account_id: evm_account.id(),
+        nonce: ZERO,
+        tx_summary: random_message(),
+    };
+    let advice_map = sign_action(&action, &owner);
+    let first = submit_action(&mut client, evm_account.id(), advice_map.clone()).await;
+    assert!(first.is_ok());
+    assert_eq!(
+        get_eth_action_nonce(&client, evm_account.id()).await?,
+        Felt::new(1)
+    );
+
+    // the same signed action is rejected once its nonce has been consumed
+    let replay = submit_action(&mut client, evm_account.id(), advice_map).await;
+    assert!(replay.is_err());
+    assert_eq!(
+        get_eth_action_nonce(&client, evm_account.id()).await?,
+        Felt::new(1)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use k256::ecdsa::{SigningKey, VerifyingKey};
+use miden_client::account::{AccountStorageMode, StorageSlot};
+use miden_client::rpc::Endpoint;
+use miden_client::{ClientError, Word, ZERO};
+use miden_objects::account::NetworkId;
+use sha3::{Digest, Keccak256};
+
+use masm_project_template::codec::bytes_to_le_felts;
+use masm_project_template::common::{
+    build_and_submit_tx, create_evm_account_with_address, delete_keystore_and_store,
+    instantiate_client, prepare_script,
+};
+use masm_project_template::constants::{
+    EVM_CODE_PATH, EVM_LIBRARY_PATH, NETWORK_ID, VERIFY_ETH_ADDRESS_SCRIPT_PATH,
+};
+use masm_project_template::ecdsa::{
+    EcdsaAdviceInputs, EcdsaError, eth_address, eth_address_storage_slots,
+};
+
+// Key and address of the web3.js accounts documentation
+const REFERENCE_PRIVATE_KEY: &str =
+    "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+const REFERENCE_ADDRESS: &str = "2c7536e3605d9c16a7a3d7b1898e529396a65c23";
+
+fn random_signing_key() -> SigningKey {
+    loop {
+        if let Ok(key) = SigningKey::from_bytes(&rand::random::<[u8; 32]>().into()) {
+            return key;
+        }
+    }
+}
+
+/// Signs `prehash` as an Ethereum wallet does, returning `r || s || v` with `v` in 27..=28.
+fn eth_sign(signing_key: &SigningKey, prehash: &[u8; 32]) -> [u8; 65] {
+    let (signature, recovery_id) = signing_key.sign_prehash_recoverable(prehash).unwrap();
+    let mut eth_signature = [0u8; 65];
+    eth_signature[..64].copy_from_slice(&signature.to_bytes());
+    eth_signature[64] = recovery_id.to_byte() + 27;
+    eth_signature
+}
+
+/// Has an EVM account storing the address of `owner` verify a signature of `signer`. Returns the
+/// result of the verifying transaction.
+async fn verify_on_chain(owner: &VerifyingKey, signer: &SigningKey) -> Result<(), ClientError> {
+    delete_keystore_and_store().await;
+
+    let endpoint = if NETWORK_ID == NetworkId::Testnet {
+        Endpoint::testnet()
+    } else {
+        Endpoint::devnet()
+    };
+    let (mut client, _) = instantiate_client(endpoint).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let evm_account = create_evm_account_with_address(
+        &mut client,
+        AccountStorageMode::Public,
+        &eth_address(owner),
+    )
+    .await
+    .unwrap();
+    println!("evm account: {:?}", evm_account.id());
+
+    let message: [u8; 32] = rand::random();
+    let prehash: [u8; 32] = Keccak256::digest(message).into();
+    let inputs =
+        EcdsaAdviceInputs::from_eth_signature(&eth_sign(signer, &prehash), &prehash).unwrap();
+
+    let tx_script = prepare_script(
+        VERIFY_ETH_ADDRESS_SCRIPT_PATH,
+        EVM_CODE_PATH,
+        EVM_LIBRARY_PATH,
+    )
+    .unwrap();
+    build_and_submit_tx(
+        tx_script,
+        inputs.to_advice_map(),
+        &mut client,
+        evm_account.id(),
+    )
+    .await
+}
+
+#[test]
+fn eth_address_reference() {
+    let signing_key = SigningKey::from_slice(&hex::decode(REFERENCE_PRIVATE_KEY).unwrap()).unwrap();
+    let address = eth_address(signing_key.verifying_key());
+    assert_eq!(hex::encode(address), REFERENCE_ADDRESS);
+
+    // the storage holds the last 5 little-endian limbs of the keccak256 digest of the public key
+    let point = signing_key.verifying_key().to_encoded_point(false);
+    let limbs = bytes_to_le_felts(&Keccak256::digest(&point.as_bytes()[1..]));
+    let slots = eth_address_storage_slots(&address);
+    assert_eq!(
+        slots[0],
+        StorageSlot::Value(Word::from([limbs[6], limbs[5], limbs[4], limbs[3]]))
+    );
+    assert_eq!(
+        slots[1],
+        StorageSlot::Value(Word::from([ZERO, ZERO, ZERO, limbs[7]]))
+    );
+}
+
+#[test]
+fn eth_signature_recovers_signer() {
+    let signing_key = random_signing_key();
+    let prehash: [u8; 32] = Keccak256::digest(b"transfer 100 tokens").into();
+    let eth_signature = eth_sign(&signing_key, &prehash);
+
+    let (signature, _) = signing_key.sign_prehash_recoverable(&prehash).unwrap();
+    let expected = EcdsaAdviceInputs::from_k256(signing_key.verifying_key(), &signature, &prehash);
+    assert_eq!(
+        EcdsaAdviceInputs::from_eth_signature(&eth_signature, &prehash),
+        Ok(expected)
+    );
+
+    // v is accepted as 0 or 1 too
+    let mut raw_v_signature = eth_signature;
+    raw_v_signature[64] -= 27;
+    assert_eq!(
+        EcdsaAdviceInputs::from_eth_signature(&raw_v_signature, &prehash),
+        Ok(expected)
+    );
+
+    let mut invalid_v_signature = eth_signature;
+    invalid_v_signature[64] = 5;
+    assert_eq!(
+        EcdsaAdviceInputs::from_eth_signature(&invalid_v_signature, &prehash),
+        Err(EcdsaError::InvalidRecoveryId(5))
+    );
+}
+
+#[tokio::test]
+async fn verify_eth_address_success() -> Result<(), Box<dyn std::error::Error>> {
+    let owner = random_signing_key();
+    verify_on_chain(owner.verifying_key(), &owner).await?;
+
+    println!("verify eth address success");
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_eth_address_wrong_signer() -> Result<(), Box<dyn std::error::Error>> {
+    let owner = random_signing_key();
+    let result = verify_on_chain(owner.verifying_key(), &random_signing_key()).await;
+    assert!(result.is_err());
+
+    Ok(())
+}
@@ -0,0 +1,255 @@
+use std::time::Duration;
+
+use masm_project_template::common::delete_keystore_and_store;
+use masm_project_template::constants::SYNC_STATE_WAIT_TIME;
+use masm_project_template::limit_order::LimitOrder;
+use masm_project_template::{
+    common::{instantiate_client, setup_accounts_and_faucets},
+    constants::NETWORK_ID,
+};
+use miden_client::account::Account;
+use miden_client::keystore::FilesystemKeyStore;
+use miden_client::note::{NoteAssets, NoteId};
+use miden_client::rpc::Endpoint;
+use miden_client::transaction::{OutputNote, TransactionRequest};
+use miden_client::{
+    Felt, Word, ZERO,
+    asset::{Asset, FungibleAsset},
+    transaction::TransactionRequestBuilder,
+};
+use miden_objects::account::NetworkId;
+use rand::rngs::StdRng;
+use tokio::time::sleep;
+
+type Client = miden_client::Client<FilesystemKeyStore<StdRng>>;
+
+// Alice sells 100 tokens of A for 200 tokens of B, i.e. 2 B per A
+const OFFERED_AMOUNT: u64 = 100;
+const REQUESTED_AMOUNT: u64 = 200;
+const BOB_BALANCE_B: u64 = 300;
+
+/// Alice places a limit order selling `OFFERED_AMOUNT` A for `REQUESTED_AMOUNT` B. Returns the
+/// client, Alice, Bob, the faucets of A and B and the order.
+async fn place_order()
+-> Result<(Client, Account, Account, Account, Account, LimitOrder), Box<dyn std::error::Error>> {
+    delete_keystore_and_store().await;
+
+    // -------------------------------------------------------------------------
+    // 1. Instantiate client
+    // -------------------------------------------------------------------------
+    let endpoint = if NETWORK_ID == NetworkId::Testnet {
+        Endpoint::testnet()
+    } else {
+        Endpoint::devnet()
+    };
+
+    let (mut client, keystore) = instantiate_client(endpoint).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let balances = vec![
+        vec![OFFERED_AMOUNT, 0], // For account[0] => Alice
+        vec![0, BOB_BALANCE_B],  // For account[1] => Bob
+    ];
+    let (accounts, faucets) =
+        setup_accounts_and_faucets(&mut client, keystore, 2, 2, balances).await?;
+
+    // rename for clarity
+    let alice_account = accounts[0].clone();
+    let bob_account = accounts[1].clone();
+    let faucet_a = faucets[0].clone();
+    let faucet_b = faucets[1].clone();
+
+    // -------------------------------------------------------------------------
+    // 2. Alice places the order
+    // -------------------------------------------------------------------------
+    let serial_num = client.rng().inner_mut().draw_word();
+    let order = LimitOrder::new(
+        alice_account.id(),
+        FungibleAsset::new(faucet_a.id(), OFFERED_AMOUNT).unwrap(),
+        FungibleAsset::new(faucet_b.id(), REQUESTED_AMOUNT).unwrap(),
+        serial_num,
+    )?;
+    assert_eq!(order.price(), (2, 1));
+
+    let tx_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(order.note().clone())])
+        .build()
+        .unwrap();
+    let tx_exec = client
+        .new_transaction(alice_account.id(), tx_request)
+        .await?;
+    client.submit_transaction(tx_exec).await?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    Ok((
+        client,
+        alice_account,
+        bob_account,
+        faucet_a,
+        faucet_b,
+        order,
+    ))
+}
+
+/// Executes `request` against the order with `account`. Returns the notes created by the
+/// transaction.
+async fn consume_order(
+    client: &mut Client,
+    account: &Account,
+    request: TransactionRequest,
+) -> Result<Vec<OutputNote>, Box<dyn std::error::Error>> {
+    let tx_exec = client.new_transaction(account.id(), request).await?;
+    client.submit_transaction(tx_exec.clone()).await?;
+    let created_notes = tx_exec.created_notes().iter().cloned().collect();
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    Ok(created_notes)
+}
+
+async fn balance(client: &Client, account: &Account, faucet: &Account) -> u64 {
+    client
+        .get_account(account.id())
+        .await
+        .unwrap()
+        .expect("account not found")
+        .account()
+        .vault()
+        .get_balance(faucet.id())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn limit_order_partial_fills_success() -> Result<(), Box<dyn std::error::Error>> {
+    let (mut client, alice_account, bob_account, faucet_a, faucet_b, order) = place_order().await?;
+
+    // -------------------------------------------------------------------------
+    // 3. Bob fills a quarter of the order
+    // -------------------------------------------------------------------------
+    let fill_amount = 50;
+    assert_eq!(order.fill_amount_out(fill_amount)?, 25);
+    let remainder = order
+        .remainder(bob_account.id(), fill_amount)?
+        .expect("order should not be filled");
+    assert_eq!(remainder.offered_asset().amount(), 75);
+    assert_eq!(remainder.price(), order.price());
+
+    let created_notes = consume_order(
+        &mut client,
+        &bob_account,
+        order.fill_request(bob_account.id(), fill_amount)?,
+    )
+    .await?;
+    assert_eq!(created_notes.len(), 2);
+    // Alice is paid the fill amount of B
+    let payment = NoteAssets::new(vec![Asset::Fungible(
+        FungibleAsset::new(faucet_b.id(), fill_amount).unwrap(),
+    )])?;
+    assert_eq!(
+        created_notes[0].id(),
+        NoteId::new(order.maker_p2id_recipient()?.digest(), payment.commitment())
+    );
+    assert_eq!(created_notes[1].id(), remainder.note().id());
+
+    assert_eq!(balance(&client, &bob_account, &faucet_a).await, 25);
+    assert_eq!(
+        balance(&client, &bob_account, &faucet_b).await,
+        BOB_BALANCE_B - fill_amount
+    );
+
+    // -------------------------------------------------------------------------
+    // 4. Bob fills the remainder order
+    // -------------------------------------------------------------------------
+    let fill_amount = remainder.full_fill_amount();
+    assert_eq!(fill_amount, 150);
+    assert!(
+        remainder
+            .remainder(bob_account.id(), fill_amount)?
+            .is_none()
+    );
+
+    let created_notes = consume_order(
+        &mut client,
+        &bob_account,
+        remainder.fill_request(bob_account.id(), fill_amount)?,
+    )
+    .await?;
+    assert_eq!(created_notes.len(), 1);
+    let payment = NoteAssets::new(vec![Asset::Fungible(
+        FungibleAsset::new(faucet_b.id(), fill_amount).unwrap(),
+    )])?;
+    assert_eq!(
+        created_notes[0].id(),
+        NoteId::new(
+            remainder.maker_p2id_recipient()?.digest(),
+            payment.commitment()
+        )
+    );
+
+    assert_eq!(
+        balance(&client, &bob_account, &faucet_a).await,
+        OFFERED_AMOUNT
+    );
+    assert_eq!(
+        balance(&client, &bob_account, &faucet_b).await,
+        BOB_BALANCE_B - REQUESTED_AMOUNT
+    );
+    assert_eq!(balance(&client, &alice_account, &faucet_a).await, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn limit_order_cancel_success() -> Result<(), Box<dyn std::error::Error>> {
+    let (mut client, alice_account, _, faucet_a, _, order) = place_order().await?;
+    assert_eq!(balance(&client, &alice_account, &faucet_a).await, 0);
+
+    let created_notes = consume_order(&mut client, &alice_account, order.cancel_request()?).await?;
+    assert!(created_notes.is_empty());
+    assert_eq!(
+        balance(&client, &alice_account, &faucet_a).await,
+        OFFERED_AMOUNT
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn limit_order_overfill() -> Result<(), Box<dyn std::error::Error>> {
+    let (mut client, _, bob_account, _, _, order) = place_order().await?;
+
+    // -------------------------------------------------------------------------
+    // 3. Bob fills a quarter of the order
+    // -------------------------------------------------------------------------
+    let fill_amount = 50;
+    let remainder = order
+        .remainder(bob_account.id(), fill_amount)?
+        .expect("order should not be filled");
+    let fill = consume_order(
+        &mut client,
+        &bob_account,
+        order.fill_request(bob_account.id(), fill_amount)?,
+    )
+    .await;
+    assert!(fill.is_ok());
+
+    // -------------------------------------------------------------------------
+    // 4. Bob overfills the remainder order
+    // -------------------------------------------------------------------------
+    // 152 B buy 76 A, one more than left: the builder refuses the fill, so the note args are
+    // passed directly to the script
+    let fill_amount = remainder.full_fill_amount() + 2;
+    assert!(remainder.fill_amount_out(fill_amount).is_err());
+    let args = Word::from([Felt::new(fill_amount), ZERO, ZERO, ZERO]);
+    let request = TransactionRequestBuilder::new()
+        .unauthenticated_input_notes([(remainder.note().clone(), Some(args))])
+        .build()?;
+
+    let overfill = consume_order(&mut client, &bob_account, request).await;
+    assert!(overfill.is_err());
+
+    Ok(())
+}
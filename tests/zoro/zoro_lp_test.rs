@@ -0,0 +1,271 @@
+use std::time::Duration;
+
+use masm_project_template::common::delete_keystore_and_store;
+use masm_project_template::constants::SYNC_STATE_WAIT_TIME;
+use masm_project_template::zoro_pool::{
+    PoolState, create_lp_deposit_note, create_lp_withdraw_note, create_zoro_pool_account,
+    fund_zoro_pool, get_lp_shares, get_pool_state, lp_withdraw_p2id_recipient,
+};
+use masm_project_template::{
+    common::{instantiate_client, setup_accounts_and_faucets},
+    constants::NETWORK_ID,
+};
+use miden_client::account::Account;
+use miden_client::keystore::FilesystemKeyStore;
+use miden_client::rpc::Endpoint;
+use miden_client::transaction::OutputNote;
+use miden_client::{
+    asset::{Asset, FungibleAsset},
+    transaction::TransactionRequestBuilder,
+};
+use miden_objects::account::NetworkId;
+use rand::rngs::StdRng;
+use tokio::time::sleep;
+
+type Client = miden_client::Client<FilesystemKeyStore<StdRng>>;
+
+const LP_DEPOSIT: u64 = 1000;
+// the pool has earned fees: each share is worth 2 tokens
+const INITIAL_POOL_STATE: PoolState = PoolState {
+    liabilities: 500,
+    reserve: 1000,
+    reserve_with_slippage: 1000,
+};
+
+/// The LP deposits `LP_DEPOSIT` tokens into a pool already holding `INITIAL_POOL_STATE`, paired
+/// with an empty pool of another asset. Returns the client, the LP, the pool, the faucet of the
+/// deposited asset and the faucet of the other asset, of which the LP holds `LP_DEPOSIT` tokens.
+async fn deposit()
+-> Result<(Client, Account, Account, Account, Account), Box<dyn std::error::Error>> {
+    delete_keystore_and_store().await;
+
+    // -------------------------------------------------------------------------
+    // 1. Instantiate client
+    // -------------------------------------------------------------------------
+    let endpoint = if NETWORK_ID == NetworkId::Testnet {
+        Endpoint::testnet()
+    } else {
+        Endpoint::devnet()
+    };
+
+    let (mut client, keystore) = instantiate_client(endpoint).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let balances = vec![
+        vec![LP_DEPOSIT, LP_DEPOSIT], // For account[0] => LP
+    ];
+    let (accounts, faucets) =
+        setup_accounts_and_faucets(&mut client, keystore.clone(), 1, 2, balances).await?;
+
+    // rename for clarity
+    let lp_account = accounts[0].clone();
+    let faucet = faucets[0].clone();
+    let other_faucet = faucets[1].clone();
+
+    // -------------------------------------------------------------------------
+    // 2. Deploy and fund the pool
+    // -------------------------------------------------------------------------
+    let (pool_account, _) = create_zoro_pool_account(
+        &mut client,
+        keystore,
+        lp_account.id(),
+        [
+            (faucet.id(), INITIAL_POOL_STATE),
+            (other_faucet.id(), PoolState::default()),
+        ],
+    )
+    .await?;
+    client.sync_state().await?;
+    fund_zoro_pool(
+        &mut client,
+        &faucet,
+        &lp_account,
+        pool_account.id(),
+        INITIAL_POOL_STATE.reserve,
+    )
+    .await?;
+
+    // -------------------------------------------------------------------------
+    // 3. The LP deposits into the pool
+    // -------------------------------------------------------------------------
+    let asset = Asset::Fungible(FungibleAsset::new(faucet.id(), LP_DEPOSIT).unwrap());
+    submit_deposit(&mut client, &lp_account, &pool_account, asset)
+        .await
+        .unwrap();
+
+    let shares = INITIAL_POOL_STATE.deposit_shares(LP_DEPOSIT).unwrap();
+    assert_eq!(shares, 500);
+    assert_eq!(
+        get_lp_shares(&client, pool_account.id(), faucet.id(), lp_account.id()).await?,
+        shares
+    );
+    assert_eq!(
+        get_pool_state(&client, pool_account.id(), faucet.id()).await?,
+        PoolState {
+            liabilities: INITIAL_POOL_STATE.liabilities + shares,
+            reserve: INITIAL_POOL_STATE.reserve + LP_DEPOSIT,
+            reserve_with_slippage: INITIAL_POOL_STATE.reserve_with_slippage + LP_DEPOSIT,
+        }
+    );
+
+    Ok((client, lp_account, pool_account, faucet, other_faucet))
+}
+
+/// The LP sends `asset` to the pool in a deposit note, which the pool then consumes.
+async fn submit_deposit(
+    client: &mut Client,
+    lp_account: &Account,
+    pool_account: &Account,
+    asset: Asset,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let serial_num = client.rng().inner_mut().draw_word();
+    let deposit_note =
+        create_lp_deposit_note(lp_account.id(), pool_account.id(), asset, serial_num)?;
+
+    let tx_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(deposit_note.clone())])
+        .build()
+        .unwrap();
+    let tx_exec = client.new_transaction(lp_account.id(), tx_request).await?;
+    client.submit_transaction(tx_exec).await?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    let consume_req = TransactionRequestBuilder::new()
+        .unauthenticated_input_notes([(deposit_note, None)])
+        .build()
+        .unwrap();
+    let tx_exec = client
+        .new_transaction(pool_account.id(), consume_req)
+        .await?;
+    client.submit_transaction(tx_exec).await?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    Ok(())
+}
+
+/// The LP withdraws `shares` from the pool. Returns the amount paid out to the LP.
+async fn withdraw(
+    client: &mut Client,
+    lp_account: &Account,
+    pool_account: &Account,
+    faucet: &Account,
+    shares: u64,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    let serial_num = client.rng().inner_mut().draw_word();
+    let withdraw_note = create_lp_withdraw_note(
+        lp_account.id(),
+        pool_account.id(),
+        faucet.id(),
+        shares,
+        serial_num,
+    )?;
+
+    let tx_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(withdraw_note.clone())])
+        .build()
+        .unwrap();
+    let tx_exec = client.new_transaction(lp_account.id(), tx_request).await?;
+    client.submit_transaction(tx_exec).await?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    let consume_req = TransactionRequestBuilder::new()
+        .unauthenticated_input_notes([(withdraw_note.clone(), None)])
+        .expected_output_recipients(vec![lp_withdraw_p2id_recipient(&withdraw_note)?])
+        .build()
+        .unwrap();
+    let tx_exec = client
+        .new_transaction(pool_account.id(), consume_req)
+        .await?;
+    client.submit_transaction(tx_exec.clone()).await?;
+
+    let payout = tx_exec.created_notes().get_note(0);
+    let amount = payout
+        .assets()
+        .unwrap()
+        .iter()
+        .next()
+        .unwrap()
+        .unwrap_fungible()
+        .amount();
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    Ok(amount)
+}
+
+#[tokio::test]
+async fn zoro_lp_deposit_and_withdraw_success() -> Result<(), Box<dyn std::error::Error>> {
+    let (mut client, lp_account, pool_account, faucet, _) = deposit().await?;
+
+    // -------------------------------------------------------------------------
+    // 4. The LP withdraws half of its shares
+    // -------------------------------------------------------------------------
+    let pool_state = get_pool_state(&client, pool_account.id(), faucet.id()).await?;
+    let expected_amount = pool_state.withdrawal_amount(250);
+
+    let amount = withdraw(&mut client, &lp_account, &pool_account, &faucet, 250).await?;
+    println!("withdrawn amount: {:?}", amount);
+    assert_eq!(amount, expected_amount);
+    assert_eq!(amount, 500);
+
+    assert_eq!(
+        get_lp_shares(&client, pool_account.id(), faucet.id(), lp_account.id()).await?,
+        250
+    );
+    assert_eq!(
+        get_pool_state(&client, pool_account.id(), faucet.id()).await?,
+        PoolState {
+            liabilities: pool_state.liabilities - 250,
+            reserve: pool_state.reserve - amount,
+            reserve_with_slippage: pool_state.reserve_with_slippage - amount,
+        }
+    );
+
+    let pool_account_state = client
+        .get_account(pool_account.id())
+        .await?
+        .expect("pool account not found");
+    let balance_pool = pool_account_state
+        .account()
+        .vault()
+        .get_balance(faucet.id())
+        .unwrap();
+    assert_eq!(balance_pool, pool_state.reserve - amount);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[should_panic]
+async fn zoro_lp_withdraw_more_than_shares() {
+    let (mut client, lp_account, pool_account, faucet, _) = deposit().await.unwrap();
+
+    // the LP owns 500 shares only
+    withdraw(&mut client, &lp_account, &pool_account, &faucet, 501)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn zoro_lp_deposit_into_empty_pool_fails() -> Result<(), Box<dyn std::error::Error>> {
+    let (mut client, lp_account, pool_account, _, other_faucet) = deposit().await.unwrap();
+
+    // the pool of the other asset has not been initialized by the admin
+    let asset = Asset::Fungible(FungibleAsset::new(other_faucet.id(), LP_DEPOSIT).unwrap());
+    let result = submit_deposit(&mut client, &lp_account, &pool_account, asset).await;
+    assert!(result.is_err());
+
+    assert_eq!(
+        get_pool_state(&client, pool_account.id(), other_faucet.id()).await?,
+        PoolState::default()
+    );
+
+    Ok(())
+}
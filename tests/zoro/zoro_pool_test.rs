@@ -0,0 +1,187 @@
+use std::time::Duration;
+
+use masm_project_template::common::{
+    delete_keystore_and_store, initialize_client_and_multisig, setup_accounts_and_faucets,
+};
+use masm_project_template::constants::SYNC_STATE_WAIT_TIME;
+use masm_project_template::zoro_pool::{
+    PoolAdminAction, PoolState, create_zoro_pool_account, create_zoro_pool_admin_note,
+    get_pool_admin, get_pool_state,
+};
+use miden_client::account::{Account, AccountId};
+use miden_client::keystore::FilesystemKeyStore;
+use miden_client::transaction::{OutputNote, TransactionRequestBuilder};
+use rand::rngs::StdRng;
+use tokio::time::sleep;
+
+type Client = miden_client::Client<FilesystemKeyStore<StdRng>>;
+
+const INITIAL_POOL_STATE: PoolState = PoolState {
+    liabilities: 1000,
+    reserve: 0,
+    reserve_with_slippage: 1000,
+};
+const NEW_POOL_STATE: PoolState = PoolState {
+    liabilities: 2000,
+    reserve: 0,
+    reserve_with_slippage: 1500,
+};
+
+/// Deploys a pool administered by the multisig. Returns the client, the multisig, another account,
+/// the pool and the faucet of the asset whose pool state is checked, the other asset of the pool is
+/// left empty.
+async fn setup_pool()
+-> Result<(Client, Account, Account, Account, Account), Box<dyn std::error::Error>> {
+    delete_keystore_and_store().await;
+
+    // -------------------------------------------------------------------------
+    // 1. Instantiate client and multisig
+    // -------------------------------------------------------------------------
+    let (mut client, multisig_contract, _, _, _) = initialize_client_and_multisig().await?;
+    let keystore = FilesystemKeyStore::new("./keystore".into()).unwrap();
+
+    let balances = vec![
+        vec![0, 0], // For account[0] => Bob
+    ];
+    let (accounts, faucets) =
+        setup_accounts_and_faucets(&mut client, keystore.clone(), 1, 2, balances).await?;
+
+    // rename for clarity
+    let bob_account = accounts[0].clone();
+    let faucet = faucets[0].clone();
+    let other_faucet = faucets[1].clone();
+
+    // -------------------------------------------------------------------------
+    // 2. Deploy the pool
+    // -------------------------------------------------------------------------
+    let (pool_account, _) = create_zoro_pool_account(
+        &mut client,
+        keystore,
+        multisig_contract.id(),
+        [
+            (faucet.id(), INITIAL_POOL_STATE),
+            (other_faucet.id(), PoolState::default()),
+        ],
+    )
+    .await?;
+    client.sync_state().await?;
+
+    assert_eq!(
+        get_pool_admin(&client, pool_account.id()).await?,
+        multisig_contract.id()
+    );
+    assert_eq!(
+        get_pool_state(&client, pool_account.id(), faucet.id()).await?,
+        INITIAL_POOL_STATE
+    );
+
+    Ok((client, multisig_contract, bob_account, pool_account, faucet))
+}
+
+/// Sends an admin note applying `action` from `sender`, then consumes it with the pool.
+async fn apply_admin_action(
+    client: &mut Client,
+    sender: AccountId,
+    pool_id: AccountId,
+    action: PoolAdminAction,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let serial_num = client.rng().inner_mut().draw_word();
+    let admin_note = create_zoro_pool_admin_note(sender, pool_id, action, serial_num)?;
+
+    let tx_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(admin_note.clone())])
+        .build()
+        .unwrap();
+    let tx_exec = client.new_transaction(sender, tx_request).await?;
+    client.submit_transaction(tx_exec).await?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    let consume_req = TransactionRequestBuilder::new()
+        .unauthenticated_input_notes([(admin_note, None)])
+        .build()
+        .unwrap();
+    let tx_exec = client.new_transaction(pool_id, consume_req).await?;
+    client.submit_transaction(tx_exec).await?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn zoro_pool_admin_set_pool_state_success() -> Result<(), Box<dyn std::error::Error>> {
+    let (mut client, multisig_contract, _, pool_account, faucet) = setup_pool().await?;
+
+    apply_admin_action(
+        &mut client,
+        multisig_contract.id(),
+        pool_account.id(),
+        PoolAdminAction::SetPoolState {
+            faucet_id: faucet.id(),
+            state: NEW_POOL_STATE,
+        },
+    )
+    .await?;
+
+    let pool_state = get_pool_state(&client, pool_account.id(), faucet.id()).await?;
+    println!("pool state: {:?}", pool_state);
+    assert_eq!(pool_state, NEW_POOL_STATE);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn zoro_pool_admin_handover_success() -> Result<(), Box<dyn std::error::Error>> {
+    let (mut client, multisig_contract, bob_account, pool_account, faucet) = setup_pool().await?;
+
+    apply_admin_action(
+        &mut client,
+        multisig_contract.id(),
+        pool_account.id(),
+        PoolAdminAction::SetAdmin(bob_account.id()),
+    )
+    .await?;
+    assert_eq!(
+        get_pool_admin(&client, pool_account.id()).await?,
+        bob_account.id()
+    );
+
+    // the new admin can set the pool states
+    apply_admin_action(
+        &mut client,
+        bob_account.id(),
+        pool_account.id(),
+        PoolAdminAction::SetPoolState {
+            faucet_id: faucet.id(),
+            state: NEW_POOL_STATE,
+        },
+    )
+    .await?;
+    assert_eq!(
+        get_pool_state(&client, pool_account.id(), faucet.id()).await?,
+        NEW_POOL_STATE
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+#[should_panic]
+async fn zoro_pool_admin_note_from_non_admin() {
+    let (mut client, _, bob_account, pool_account, faucet) = setup_pool().await.unwrap();
+
+    apply_admin_action(
+        &mut client,
+        bob_account.id(),
+        pool_account.id(),
+        PoolAdminAction::SetPoolState {
+            faucet_id: faucet.id(),
+            state: NEW_POOL_STATE,
+        },
+    )
+    .await
+    .unwrap();
+}
@@ -0,0 +1,226 @@
+use masm_project_template::zoro_pool::PoolState;
+use masm_project_template::zoro_quote::{
+    BPS, QuoteError, RefundReason, price_impact_bps, quote_amount_out, quote_swap,
+};
+use masm_project_template::zoro_swap::{ZoroSwapNote, ZoroSwapOutcome};
+use miden_client::account::AccountId;
+use miden_client::asset::{Asset, FungibleAsset};
+use miden_client::block::BlockNumber;
+use miden_client::{Felt, Word};
+use miden_objects::testing::account_id::{
+    ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET, ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_1,
+    ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
+    ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE,
+};
+use proptest::prelude::*;
+
+const DEADLINE: u32 = 1000;
+// same pools as zoro_swap_test.rs: 100 tokens of A buy 90 tokens of B
+const POOL_IN: PoolState = PoolState {
+    liabilities: 1000,
+    reserve: 0,
+    reserve_with_slippage: 1000,
+};
+const POOL_OUT: PoolState = PoolState {
+    liabilities: 1000,
+    reserve: 500,
+    reserve_with_slippage: 1000,
+};
+
+fn swap_note(amount_in: u64, min_amount_out: u64) -> ZoroSwapNote {
+    let creator = AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap();
+    let faucet_a = AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET).unwrap();
+    let faucet_b = AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_1).unwrap();
+    let pool = AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE).unwrap();
+    ZoroSwapNote::new(
+        creator,
+        pool,
+        Asset::Fungible(FungibleAsset::new(faucet_a, amount_in).unwrap()),
+        faucet_b,
+        min_amount_out,
+        BlockNumber::from(DEADLINE),
+        Word::from([Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)]),
+    )
+    .unwrap()
+}
+
+#[test]
+fn zoro_quote_executed_swap() {
+    let quote = quote_swap(
+        &swap_note(100, 50),
+        POOL_IN,
+        POOL_OUT,
+        BlockNumber::from(DEADLINE),
+    )
+    .unwrap();
+
+    assert_eq!(quote.amount_out, 90);
+    assert_eq!(quote.outcome, ZoroSwapOutcome::Executed { amount_out: 90 });
+    assert_eq!(quote.refund_reason, None);
+    // 90 B for 100 A at a spot price of 1
+    assert_eq!(quote.price_impact_bps, 1000);
+    assert_eq!(quote.pool_in_after.reserve, 100);
+    assert_eq!(quote.pool_in_after.reserve_with_slippage, 1100);
+    assert_eq!(quote.pool_out_after.reserve, 410);
+    assert_eq!(quote.pool_out_after.reserve_with_slippage, 910);
+
+    // amounts beyond u32 are computed on 128 bits
+    let pool_in = PoolState {
+        reserve_with_slippage: 1 << 40,
+        ..POOL_IN
+    };
+    let pool_out = PoolState {
+        reserve_with_slippage: 3 << 40,
+        ..POOL_OUT
+    };
+    assert_eq!(quote_amount_out(1 << 40, &pool_in, &pool_out), Ok(3 << 39));
+}
+
+#[test]
+fn zoro_quote_liabilities_on_the_curve() {
+    // pools covering half of their liabilities are priced on half of their reserve with slippage
+    let under_covered = |pool: PoolState| PoolState {
+        liabilities: 2 * pool.reserve_with_slippage,
+        ..pool
+    };
+    assert_eq!(under_covered(POOL_OUT).virtual_reserve(), 500);
+
+    // 500 * 100 / (1000 + 100), less out of an under-covered pool
+    assert_eq!(
+        quote_amount_out(100, &POOL_IN, &under_covered(POOL_OUT)),
+        Ok(45)
+    );
+    // 1000 * 100 / (500 + 100), more for refilling an under-covered pool
+    assert_eq!(
+        quote_amount_out(100, &under_covered(POOL_IN), &POOL_OUT),
+        Ok(166)
+    );
+    // pools covering more than their liabilities are priced on their reserve with slippage
+    let over_covered = PoolState {
+        liabilities: 500,
+        ..POOL_OUT
+    };
+    assert_eq!(quote_amount_out(100, &POOL_IN, &over_covered), Ok(90));
+}
+
+#[test]
+fn zoro_quote_refunds() {
+    let note = swap_note(100, 50);
+    let refund = ZoroSwapOutcome::Refunded(note.offered_asset());
+
+    let quote = quote_swap(&note, POOL_IN, POOL_OUT, BlockNumber::from(DEADLINE + 1)).unwrap();
+    assert_eq!(quote.outcome, refund);
+    assert_eq!(quote.refund_reason, Some(RefundReason::DeadlinePassed));
+
+    let quote = quote_swap(
+        &swap_note(100, 91),
+        POOL_IN,
+        POOL_OUT,
+        BlockNumber::from(DEADLINE),
+    )
+    .unwrap();
+    assert_eq!(quote.amount_out, 90);
+    assert_eq!(quote.refund_reason, Some(RefundReason::MinAmountOutNotMet));
+
+    let pool_out = PoolState {
+        reserve: 89,
+        ..POOL_OUT
+    };
+    let quote = quote_swap(&note, POOL_IN, pool_out, BlockNumber::from(DEADLINE)).unwrap();
+    assert_eq!(quote.outcome, refund);
+    assert_eq!(quote.refund_reason, Some(RefundReason::InsufficientReserve));
+    assert_eq!(quote.pool_in_after, POOL_IN);
+    assert_eq!(quote.pool_out_after, pool_out);
+}
+
+#[test]
+fn zoro_quote_script_failures() {
+    // the input reserve with slippage plus the input amount wraps around the field
+    let pool_in = PoolState {
+        reserve_with_slippage: 0xffff_ffff_0000_0000 - 50,
+        ..POOL_IN
+    };
+    assert_eq!(
+        quote_amount_out(100, &pool_in, &POOL_OUT),
+        Err(QuoteError::Overflow)
+    );
+    assert_eq!(
+        quote_amount_out(0, &PoolState::default(), &POOL_OUT),
+        Err(QuoteError::EmptyPool)
+    );
+    // an empty input pool would pay out the whole output virtual reserve
+    assert_eq!(
+        quote_amount_out(100, &PoolState::default(), &POOL_OUT),
+        Err(QuoteError::EmptyPool)
+    );
+    assert_eq!(
+        quote_amount_out(100, &POOL_IN, &PoolState::default()),
+        Err(QuoteError::EmptyPool)
+    );
+    // a virtual reserve rounded down to zero
+    let dust = PoolState {
+        liabilities: 2,
+        reserve: 1,
+        reserve_with_slippage: 1,
+    };
+    assert_eq!(
+        quote_amount_out(100, &dust, &POOL_OUT),
+        Err(QuoteError::EmptyPool)
+    );
+
+    // crediting the input amount to the input reserves overflows the field, or u64
+    for reserve in [0xffff_ffff_0000_0000 - 50, u64::MAX - 50] {
+        let pool_in = PoolState { reserve, ..POOL_IN };
+        assert_eq!(
+            quote_swap(
+                &swap_note(100, 50),
+                pool_in,
+                POOL_OUT,
+                BlockNumber::from(DEADLINE)
+            ),
+            Err(QuoteError::Overflow)
+        );
+    }
+
+    // a deadline passed refund does not run the curve
+    let quote = quote_swap(
+        &swap_note(100, 50),
+        pool_in,
+        POOL_OUT,
+        BlockNumber::from(DEADLINE + 1),
+    )
+    .unwrap();
+    assert_eq!(quote.refund_reason, Some(RefundReason::DeadlinePassed));
+}
+
+proptest! {
+    #[test]
+    fn zoro_quote_bounds(
+        amount_in in 1..=1u64 << 62,
+        reserve_in in 1..=1u64 << 62,
+        reserve_out in 1..=1u64 << 62,
+        liabilities_in in 0..=1u64 << 62,
+        liabilities_out in 0..=1u64 << 62,
+    ) {
+        let pool_in = PoolState {
+            liabilities: liabilities_in,
+            reserve_with_slippage: reserve_in,
+            ..POOL_IN
+        };
+        let pool_out = PoolState {
+            liabilities: liabilities_out,
+            reserve_with_slippage: reserve_out,
+            ..POOL_OUT
+        };
+        let virtual_in = pool_in.virtual_reserve();
+        let virtual_out = pool_out.virtual_reserve();
+        prop_assume!(virtual_in != 0 && virtual_out != 0);
+        let amount_out = quote_amount_out(amount_in, &pool_in, &pool_out).unwrap();
+
+        // the output never drains the pool and is at most the input at the spot price
+        prop_assert!(virtual_in <= reserve_in && virtual_out <= reserve_out);
+        prop_assert!(amount_out <= virtual_out);
+        prop_assert!(amount_out as u128 * virtual_in as u128 <= amount_in as u128 * virtual_out as u128);
+        prop_assert!(price_impact_bps(amount_in, amount_out, &pool_in, &pool_out) <= BPS);
+    }
+}
@@ -0,0 +1,371 @@
+use std::time::Duration;
+
+use masm_project_template::common::delete_keystore_and_store;
+use masm_project_template::constants::SYNC_STATE_WAIT_TIME;
+use masm_project_template::zoro_pool::{
+    PoolState, create_zoro_pool_account, fund_zoro_pool, get_pool_state,
+};
+use masm_project_template::zoro_route::{RouteHop, ZoroRoute, ZoroRouteError, find_best_route};
+use masm_project_template::zoro_swap::{ZoroSwapArgs, ZoroSwapNote, ZoroSwapOutcome};
+use masm_project_template::{
+    common::{instantiate_client, setup_accounts_and_faucets},
+    constants::NETWORK_ID,
+};
+use miden_client::account::AccountId;
+use miden_client::block::BlockNumber;
+use miden_client::rpc::Endpoint;
+use miden_client::transaction::OutputNote;
+use miden_client::{
+    Word,
+    asset::{Asset, FungibleAsset},
+    transaction::TransactionRequestBuilder,
+};
+use miden_objects::account::NetworkId;
+use miden_objects::testing::account_id::{
+    ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET, ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_1,
+    ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_2, ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE,
+    ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
+    ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE,
+};
+use tokio::time::sleep;
+
+const AMOUNT_IN: u64 = 100;
+// the first pool account trades A for X, the second one X for B: 100 A buy 90 X, which buy 82 B
+const POOL_A: PoolState = PoolState {
+    liabilities: 1000,
+    reserve: 0,
+    reserve_with_slippage: 1000,
+};
+const POOL_X: PoolState = PoolState {
+    liabilities: 1000,
+    reserve: 1000,
+    reserve_with_slippage: 1000,
+};
+// the pool of X of the second pool account starts empty, as the one of A
+const POOL_X_IN: PoolState = PoolState {
+    reserve: 0,
+    ..POOL_X
+};
+const POOL_B: PoolState = PoolState {
+    liabilities: 1000,
+    reserve: 500,
+    reserve_with_slippage: 1000,
+};
+const AMOUNT_MID: u64 = 90;
+// 1000 * 90 / (1000 + 90)
+const AMOUNT_OUT: u64 = 82;
+// 1000 * 100 / (1000 + 100), on a pool account holding both A and B
+const DIRECT_AMOUNT_OUT: u64 = 90;
+
+/// Alice routes `AMOUNT_IN` tokens of A to B through X, requiring `min_amount_mid` tokens of X
+/// from the first hop and `min_amount_out` tokens of B from the second one. The first pool account
+/// holds the pools of A and X and runs the first hop, the second one holds the pools of X and B and
+/// runs the second hop. Returns the outcome of the last hop executed, read from its P2ID note, and
+/// the pool states of A and X of the first pool account and of X and B of the second one
+/// afterwards.
+async fn execute_route(
+    min_amount_mid: u64,
+    min_amount_out: u64,
+) -> Result<(ZoroSwapOutcome, [PoolState; 4]), Box<dyn std::error::Error>> {
+    delete_keystore_and_store().await;
+
+    // -------------------------------------------------------------------------
+    // 1. Instantiate client and deploy the pools
+    // -------------------------------------------------------------------------
+    let endpoint = if NETWORK_ID == NetworkId::Testnet {
+        Endpoint::testnet()
+    } else {
+        Endpoint::devnet()
+    };
+
+    let (mut client, keystore) = instantiate_client(endpoint).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let balances = vec![
+        vec![AMOUNT_IN, 0, 0], // For account[0] => Alice
+    ];
+    let (accounts, faucets) =
+        setup_accounts_and_faucets(&mut client, keystore.clone(), 1, 3, balances).await?;
+
+    // rename for clarity
+    let alice_account = accounts[0].clone();
+    let faucet_a = faucets[0].clone();
+    let faucet_x = faucets[1].clone();
+    let faucet_b = faucets[2].clone();
+
+    let first_states = [(faucet_a.id(), POOL_A), (faucet_x.id(), POOL_X)];
+    let second_states = [(faucet_x.id(), POOL_X_IN), (faucet_b.id(), POOL_B)];
+    let (first_pool, _) = create_zoro_pool_account(
+        &mut client,
+        keystore.clone(),
+        alice_account.id(),
+        first_states,
+    )
+    .await?;
+    let (second_pool, _) =
+        create_zoro_pool_account(&mut client, keystore, alice_account.id(), second_states).await?;
+    client.sync_state().await?;
+    fund_zoro_pool(
+        &mut client,
+        &faucet_x,
+        &alice_account,
+        first_pool.id(),
+        POOL_X.reserve,
+    )
+    .await?;
+    fund_zoro_pool(
+        &mut client,
+        &faucet_b,
+        &alice_account,
+        second_pool.id(),
+        POOL_B.reserve,
+    )
+    .await?;
+
+    // -------------------------------------------------------------------------
+    // 2. Alice sends the first hop along the best path
+    // -------------------------------------------------------------------------
+    let pools = [
+        (first_pool.id(), first_states),
+        (second_pool.id(), second_states),
+    ];
+    let plan =
+        find_best_route(AMOUNT_IN, faucet_a.id(), faucet_b.id(), &pools).expect("no route found");
+    assert_eq!(plan.first_pool, first_pool.id());
+    assert_eq!(plan.second_pool, Some(second_pool.id()));
+    assert_eq!(plan.mid_faucet, Some(faucet_x.id()));
+    assert_eq!(plan.amount_mid, AMOUNT_MID);
+    assert_eq!(plan.amount_out, AMOUNT_OUT);
+
+    let sync_height = client.get_sync_height().await?.as_u32();
+    let serial_num = client.rng().inner_mut().draw_word();
+    let route = ZoroRoute::new(
+        alice_account.id(),
+        Asset::Fungible(FungibleAsset::new(faucet_a.id(), AMOUNT_IN).unwrap()),
+        RouteHop {
+            pool: first_pool.id(),
+            faucet_id: faucet_x.id(),
+            min_amount_out: min_amount_mid,
+        },
+        RouteHop {
+            pool: second_pool.id(),
+            faucet_id: faucet_b.id(),
+            min_amount_out,
+        },
+        BlockNumber::from(sync_height + 100),
+        serial_num,
+    )?;
+    assert_eq!(
+        ZoroSwapNote::from_note(route.first_hop().note().clone())?,
+        *route.first_hop()
+    );
+
+    let tx_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(route.first_hop().note().clone())])
+        .build()
+        .unwrap();
+    let tx_exec = client
+        .new_transaction(alice_account.id(), tx_request)
+        .await?;
+    client.submit_transaction(tx_exec).await?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    // -------------------------------------------------------------------------
+    // 3. The first pool consumes the first hop, quoting the second one on the second pool
+    // -------------------------------------------------------------------------
+    let tx_exec = client
+        .new_transaction(
+            first_pool.id(),
+            route.first_hop_request(ZoroSwapArgs::default())?,
+        )
+        .await?;
+    client.submit_transaction(tx_exec.clone()).await?;
+
+    assert_eq!(tx_exec.created_notes().num_notes(), 1);
+    let output_note = tx_exec.created_notes().get_note(0);
+    let mut outcome = route.first_hop().decode_output(output_note)?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    // -------------------------------------------------------------------------
+    // 4. The second pool consumes the second hop, paid by the first one
+    // -------------------------------------------------------------------------
+    if outcome
+        == (ZoroSwapOutcome::Executed {
+            amount_out: AMOUNT_MID,
+        })
+    {
+        let second_hop = route.second_hop_note(output_note)?;
+        assert_eq!(second_hop.pool(), second_pool.id());
+        assert_eq!(
+            second_hop.offered_asset(),
+            Asset::Fungible(FungibleAsset::new(faucet_x.id(), AMOUNT_MID).unwrap())
+        );
+
+        let tx_exec = client
+            .new_transaction(
+                second_pool.id(),
+                second_hop.consume_request(ZoroSwapArgs::default())?,
+            )
+            .await?;
+        client.submit_transaction(tx_exec.clone()).await?;
+
+        assert_eq!(tx_exec.created_notes().num_notes(), 1);
+        outcome = second_hop.decode_output(tx_exec.created_notes().get_note(0))?;
+
+        sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+        client.sync_state().await?;
+    } else {
+        // a refunded route does not pay into the second hop
+        assert!(route.second_hop_note(output_note).is_err());
+    }
+
+    let states = [
+        get_pool_state(&client, first_pool.id(), faucet_a.id()).await?,
+        get_pool_state(&client, first_pool.id(), faucet_x.id()).await?,
+        get_pool_state(&client, second_pool.id(), faucet_x.id()).await?,
+        get_pool_state(&client, second_pool.id(), faucet_b.id()).await?,
+    ];
+    println!("pool states: {:?}", states);
+
+    Ok((outcome, states))
+}
+
+#[test]
+fn zoro_route_best_path() -> Result<(), Box<dyn std::error::Error>> {
+    let faucet_a = AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET)?;
+    let faucet_x = AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_1)?;
+    let faucet_b = AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_2)?;
+    let first_pool = AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE)?;
+    let second_pool = AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE)?;
+    let direct_pool = AccountId::try_from(ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE)?;
+
+    // B cannot be bought without a pool of B
+    let pools = [(first_pool, vec![(faucet_a, POOL_A), (faucet_x, POOL_X)])];
+    assert_eq!(find_best_route(AMOUNT_IN, faucet_a, faucet_b, &pools), None);
+
+    // A and B only meet through X, across the two pool accounts
+    let pools = [
+        (first_pool, vec![(faucet_a, POOL_A), (faucet_x, POOL_X)]),
+        (second_pool, vec![(faucet_x, POOL_X_IN), (faucet_b, POOL_B)]),
+    ];
+    let plan = find_best_route(AMOUNT_IN, faucet_a, faucet_b, &pools).unwrap();
+    assert_eq!(plan.first_pool, first_pool);
+    assert_eq!(plan.second_pool, Some(second_pool));
+    assert_eq!(plan.mid_faucet, Some(faucet_x));
+    assert_eq!(plan.amount_mid, AMOUNT_MID);
+    assert_eq!(plan.amount_out, AMOUNT_OUT);
+
+    // both hops never run on the same pool account, so a single pool account only swaps directly
+    let pools = [(
+        first_pool,
+        vec![(faucet_a, POOL_A), (faucet_x, POOL_X), (faucet_b, POOL_B)],
+    )];
+    let plan = find_best_route(AMOUNT_IN, faucet_a, faucet_b, &pools).unwrap();
+    assert_eq!(plan.first_pool, first_pool);
+    assert_eq!(plan.second_pool, None);
+    assert_eq!(plan.mid_faucet, None);
+    assert_eq!(plan.amount_out, DIRECT_AMOUNT_OUT);
+
+    // a direct swap paying more wins over the route
+    let pools = [
+        (first_pool, vec![(faucet_a, POOL_A), (faucet_x, POOL_X)]),
+        (second_pool, vec![(faucet_x, POOL_X_IN), (faucet_b, POOL_B)]),
+        (direct_pool, vec![(faucet_a, POOL_A), (faucet_b, POOL_B)]),
+    ];
+    let plan = find_best_route(AMOUNT_IN, faucet_a, faucet_b, &pools).unwrap();
+    assert_eq!(plan.first_pool, direct_pool);
+    assert_eq!(plan.mid_faucet, None);
+    assert_eq!(plan.amount_out, DIRECT_AMOUNT_OUT);
+
+    // no path is left when the pool of B cannot pay
+    let empty_b = PoolState {
+        reserve: 0,
+        ..POOL_B
+    };
+    let pools = [
+        (first_pool, vec![(faucet_a, POOL_A), (faucet_x, POOL_X)]),
+        (
+            second_pool,
+            vec![(faucet_x, POOL_X_IN), (faucet_b, empty_b)],
+        ),
+    ];
+    assert_eq!(find_best_route(AMOUNT_IN, faucet_a, faucet_b, &pools), None);
+
+    // a route needs two distinct pool accounts
+    let hop = |pool, faucet_id| RouteHop {
+        pool,
+        faucet_id,
+        min_amount_out: 1,
+    };
+    let route = ZoroRoute::new(
+        faucet_a,
+        Asset::Fungible(FungibleAsset::new(faucet_a, AMOUNT_IN).unwrap()),
+        hop(first_pool, faucet_x),
+        hop(first_pool, faucet_b),
+        BlockNumber::from(1234),
+        Word::default(),
+    );
+    assert!(matches!(route, Err(ZoroRouteError::SamePool(pool)) if pool == first_pool));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn zoro_route_two_hops_success() -> Result<(), Box<dyn std::error::Error>> {
+    let (outcome, [state_a, state_x, state_x_in, state_b]) =
+        execute_route(AMOUNT_MID, AMOUNT_OUT).await?;
+
+    assert_eq!(
+        outcome,
+        ZoroSwapOutcome::Executed {
+            amount_out: AMOUNT_OUT
+        }
+    );
+    // the first pool account sells X for A, the second one buys it back for B
+    assert_eq!(state_a.reserve, POOL_A.reserve + AMOUNT_IN);
+    assert_eq!(state_x.reserve, POOL_X.reserve - AMOUNT_MID);
+    assert_eq!(state_x_in.reserve, POOL_X_IN.reserve + AMOUNT_MID);
+    assert_eq!(state_b.reserve, POOL_B.reserve - AMOUNT_OUT);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn zoro_route_first_hop_min_not_met_refund() -> Result<(), Box<dyn std::error::Error>> {
+    let (outcome, [state_a, state_x, state_x_in, state_b]) =
+        execute_route(AMOUNT_MID + 1, AMOUNT_OUT).await?;
+
+    let ZoroSwapOutcome::Refunded(refund) = outcome else {
+        panic!("route was not refunded");
+    };
+    assert_eq!(refund.unwrap_fungible().amount(), AMOUNT_IN);
+    assert_eq!(state_a, POOL_A);
+    assert_eq!(state_x, POOL_X);
+    assert_eq!(state_x_in, POOL_X_IN);
+    assert_eq!(state_b, POOL_B);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn zoro_route_second_hop_min_not_met_refund() -> Result<(), Box<dyn std::error::Error>> {
+    let (outcome, [state_a, state_x, state_x_in, state_b]) =
+        execute_route(AMOUNT_MID, AMOUNT_OUT + 1).await?;
+
+    // the second hop is quoted before the first one is executed: the first hop is refunded, so
+    // Alice gets A back rather than X, and the second hop is never created
+    let ZoroSwapOutcome::Refunded(refund) = outcome else {
+        panic!("route was not refunded");
+    };
+    assert_eq!(refund.unwrap_fungible().amount(), AMOUNT_IN);
+    assert_eq!(state_a, POOL_A);
+    assert_eq!(state_x, POOL_X);
+    assert_eq!(state_x_in, POOL_X_IN);
+    assert_eq!(state_b, POOL_B);
+
+    Ok(())
+}
@@ -0,0 +1,779 @@
+use std::time::Duration;
+
+use masm_project_template::common::delete_keystore_and_store;
+use masm_project_template::constants::SYNC_STATE_WAIT_TIME;
+use masm_project_template::zoro_pool::{
+    PoolState, create_zoro_pool_account, fund_zoro_pool, get_pool_assets, get_pool_state,
+};
+use masm_project_template::zoro_quote::quote_swap;
+use masm_project_template::zoro_swap::{
+    ZOROSWAP_NUM_INPUTS, ZoroSwapArgs, ZoroSwapNote, ZoroSwapOutcome, find_outstanding_zoro_swaps,
+    zoro_swap_tag,
+};
+use masm_project_template::{
+    common::{instantiate_client, setup_accounts_and_faucets},
+    constants::NETWORK_ID,
+};
+use miden_client::account::AccountId;
+use miden_client::block::BlockNumber;
+use miden_client::note::{Note, NoteAssets, NoteExecutionHint, NoteMetadata, NoteTag, NoteType};
+use miden_client::rpc::Endpoint;
+use miden_client::transaction::OutputNote;
+use miden_client::{
+    Felt, Word,
+    asset::{Asset, FungibleAsset},
+    transaction::TransactionRequestBuilder,
+};
+use miden_lib::note::utils::build_p2id_recipient;
+use miden_objects::account::NetworkId;
+use miden_objects::testing::account_id::{
+    ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET, ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_1,
+    ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
+    ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE,
+};
+use tokio::time::sleep;
+
+const AMOUNT_IN: u64 = 100;
+const MIN_AMOUNT_OUT: u64 = 50;
+const POOL_RESERVE_OUT: u64 = 500;
+const RESERVE_WITH_SLIPPAGE: u64 = 1000;
+// 1000 * 100 / (1000 + 100)
+const AMOUNT_OUT: u64 = 90;
+
+/// Alice offers `AMOUNT_IN` tokens of faucet A for at least `min_amount_out` tokens of faucet B,
+/// the pool account executes the swap. With `payout_aux`, the output is paid to Bob with that aux
+/// instead of Alice, refunds still go to Alice. Returns the outcome read from the P2ID output note
+/// and the pool balances of A and B afterwards.
+async fn execute_swap(
+    min_amount_out: u64,
+    deadline_passed: bool,
+    args: ZoroSwapArgs,
+    payout_aux: Option<Felt>,
+) -> Result<(ZoroSwapOutcome, u64, u64), Box<dyn std::error::Error>> {
+    delete_keystore_and_store().await;
+
+    // -------------------------------------------------------------------------
+    // 1. Instantiate client
+    // -------------------------------------------------------------------------
+    let endpoint = if NETWORK_ID == NetworkId::Testnet {
+        Endpoint::testnet()
+    } else {
+        Endpoint::devnet()
+    };
+
+    let (mut client, keystore) = instantiate_client(endpoint).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let balances = vec![
+        vec![AMOUNT_IN, 0], // For account[0] => Alice
+        vec![0, 0],         // For account[1] => Bob
+    ];
+    let (accounts, faucets) =
+        setup_accounts_and_faucets(&mut client, keystore.clone(), 2, 2, balances).await?;
+
+    // rename for clarity
+    let alice_account = accounts[0].clone();
+    let bob_account = accounts[1].clone();
+    let faucet_a = faucets[0].clone();
+    let faucet_b = faucets[1].clone();
+
+    // the pool holds POOL_RESERVE_OUT tokens of B
+    let pool_state = PoolState {
+        liabilities: RESERVE_WITH_SLIPPAGE,
+        reserve: POOL_RESERVE_OUT,
+        reserve_with_slippage: RESERVE_WITH_SLIPPAGE,
+    };
+    // the pool of A starts empty
+    let pool_state_a = PoolState {
+        reserve: 0,
+        ..pool_state
+    };
+    let (pool_account, _) = create_zoro_pool_account(
+        &mut client,
+        keystore,
+        alice_account.id(),
+        [(faucet_a.id(), pool_state_a), (faucet_b.id(), pool_state)],
+    )
+    .await?;
+    client.sync_state().await?;
+    fund_zoro_pool(
+        &mut client,
+        &faucet_b,
+        &alice_account,
+        pool_account.id(),
+        POOL_RESERVE_OUT,
+    )
+    .await?;
+    assert_eq!(
+        get_pool_state(&client, pool_account.id(), faucet_b.id()).await?,
+        pool_state
+    );
+
+    // -------------------------------------------------------------------------
+    // 2. Alice creates the swap note
+    // -------------------------------------------------------------------------
+    let sync_height = client.get_sync_height().await?.as_u32();
+    let deadline = if deadline_passed {
+        BlockNumber::from(sync_height - 1)
+    } else {
+        BlockNumber::from(sync_height + 100)
+    };
+
+    let offered_asset = Asset::Fungible(FungibleAsset::new(faucet_a.id(), AMOUNT_IN).unwrap());
+    let serial_num = client.rng().inner_mut().draw_word();
+    let (payout_id, aux) = match payout_aux {
+        Some(aux) => (bob_account.id(), aux),
+        None => (alice_account.id(), Felt::new(0)),
+    };
+    let swap_note = ZoroSwapNote::with_payout(
+        alice_account.id(),
+        pool_account.id(),
+        offered_asset,
+        faucet_b.id(),
+        min_amount_out,
+        deadline,
+        payout_id,
+        aux,
+        serial_num,
+    )?;
+
+    let tx_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(swap_note.note().clone())])
+        .build()
+        .unwrap();
+    let tx_exec = client
+        .new_transaction(alice_account.id(), tx_request)
+        .await?;
+    client.submit_transaction(tx_exec).await?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    // -------------------------------------------------------------------------
+    // 3. The pool consumes the swap note
+    // -------------------------------------------------------------------------
+    // quote the swap locally from the on-chain pool states, to be compared with its execution
+    let quote = quote_swap(
+        &swap_note,
+        get_pool_state(&client, pool_account.id(), faucet_a.id()).await?,
+        get_pool_state(&client, pool_account.id(), faucet_b.id()).await?,
+        client.get_sync_height().await?,
+    )?;
+    println!("quote: {:?}", quote);
+
+    let consume_req = swap_note.consume_request(args)?;
+    let tx_exec = client
+        .new_transaction(pool_account.id(), consume_req)
+        .await?;
+    client.submit_transaction(tx_exec.clone()).await?;
+
+    let output_note = tx_exec.created_notes().get_note(0);
+    let output_asset = *output_note.assets().unwrap().iter().next().unwrap();
+    let outcome = swap_note.decode_output(output_note)?;
+
+    // the output note is a standard P2ID note for the payout account, or for Alice if the swap is
+    // refunded, as built by create_p2id_note, with the swap serial number incremented in its last
+    // element
+    let target_id = match outcome {
+        ZoroSwapOutcome::Executed { .. } => payout_id,
+        ZoroSwapOutcome::Refunded(_) => alice_account.id(),
+    };
+    let serial_num = swap_note.note().serial_num();
+    let p2id_serial_num = Word::from([
+        serial_num[0],
+        serial_num[1],
+        serial_num[2],
+        serial_num[3] + Felt::new(1),
+    ]);
+    let expected_metadata = NoteMetadata::new(
+        pool_account.id(),
+        NoteType::Public,
+        NoteTag::from_account_id(target_id),
+        NoteExecutionHint::always(),
+        aux,
+    )?;
+    let expected_note = Note::new(
+        NoteAssets::new(vec![output_asset])?,
+        expected_metadata,
+        build_p2id_recipient(target_id, p2id_serial_num)?,
+    );
+    assert_eq!(output_note.id(), expected_note.id());
+    assert_eq!(output_note.metadata(), expected_note.metadata());
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    let pool_account_state = client
+        .get_account(pool_account.id())
+        .await?
+        .expect("pool account not found");
+    let vault = pool_account_state.account().vault();
+    let balance_a = vault.get_balance(faucet_a.id()).unwrap();
+    let balance_b = vault.get_balance(faucet_b.id()).unwrap();
+    println!("pool balances: a = {:?}, b = {:?}", balance_a, balance_b);
+
+    // the reserves follow the vault of the pool
+    let state_a = get_pool_state(&client, pool_account.id(), faucet_a.id()).await?;
+    let state_b = get_pool_state(&client, pool_account.id(), faucet_b.id()).await?;
+    println!("pool states: a = {:?}, b = {:?}", state_a, state_b);
+    assert_eq!(state_a.reserve, balance_a);
+    assert_eq!(state_b.reserve, balance_b);
+    assert_eq!(
+        state_a.reserve_with_slippage - RESERVE_WITH_SLIPPAGE,
+        balance_a
+    );
+    assert_eq!(
+        RESERVE_WITH_SLIPPAGE - state_b.reserve_with_slippage,
+        POOL_RESERVE_OUT - balance_b
+    );
+
+    // the VM agrees with the local quote
+    assert_eq!(outcome, quote.outcome);
+    assert_eq!(state_a, quote.pool_in_after);
+    assert_eq!(state_b, quote.pool_out_after);
+
+    Ok((outcome, balance_a, balance_b))
+}
+
+#[test]
+fn zoro_swap_note_layout() -> Result<(), Box<dyn std::error::Error>> {
+    let creator = AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE)?;
+    let faucet_a = AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET)?;
+    let faucet_b = AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_1)?;
+    let pool = AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE)?;
+    let offered_asset = Asset::Fungible(FungibleAsset::new(faucet_a, AMOUNT_IN).unwrap());
+    let serial_num = Word::from([Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)]);
+
+    let swap_note = ZoroSwapNote::new(
+        creator,
+        pool,
+        offered_asset,
+        faucet_b,
+        MIN_AMOUNT_OUT,
+        BlockNumber::from(1234),
+        serial_num,
+    )?;
+
+    // inputs => [REQUESTED_ASSET_WORD, deadline, payout_tag, 0, refund_tag, CREATOR_ID_WORD,
+    //            POOL_ID_WORD, NEXT_HOP_RECIPIENT, NEXT_HOP_ASSET_WORD]
+    let inputs = swap_note.note().recipient().inputs().values();
+    assert_eq!(inputs.len(), ZOROSWAP_NUM_INPUTS);
+    assert_eq!(
+        inputs[..4],
+        [
+            Felt::new(MIN_AMOUNT_OUT),
+            Felt::new(0),
+            faucet_b.suffix(),
+            faucet_b.prefix().as_felt()
+        ]
+    );
+    assert_eq!(inputs[4], Felt::new(1234));
+    assert_eq!(inputs[5], Felt::from(NoteTag::from_account_id(creator)));
+    assert_eq!(inputs[6], Felt::new(0));
+    assert_eq!(inputs[7], Felt::from(NoteTag::from_account_id(creator)));
+    // the creator is paid unless another payout account is given
+    assert_eq!(
+        inputs[8..10],
+        [creator.suffix(), creator.prefix().as_felt()]
+    );
+    assert_eq!(
+        inputs[10..12],
+        [creator.suffix(), creator.prefix().as_felt()]
+    );
+    // only the given pool can execute the swap, there is no next pool
+    assert_eq!(
+        inputs[12..16],
+        [
+            pool.suffix(),
+            pool.prefix().as_felt(),
+            Felt::new(0),
+            Felt::new(0)
+        ]
+    );
+    // the output is paid in a P2ID note rather than into a next hop
+    assert_eq!(inputs[16..], [Felt::new(0); 8]);
+    assert_eq!(swap_note.next_hop(), None);
+    assert_eq!(swap_note.next_hop_swap(), None);
+    assert_eq!(swap_note.note().metadata().tag(), zoro_swap_tag());
+
+    // the swap parameters can be read back from the note
+    let received = ZoroSwapNote::from_note(swap_note.note().clone())?;
+    assert_eq!(received, swap_note);
+    assert_eq!(received.offered_asset(), offered_asset);
+    assert_eq!(received.deadline(), BlockNumber::from(1234));
+
+    // args => [expected_amount_out, 0, 0, 0]
+    let args = ZoroSwapArgs {
+        expected_amount_out: Some(AMOUNT_OUT),
+    };
+    assert_eq!(
+        args.to_word(),
+        Word::from([
+            Felt::new(AMOUNT_OUT),
+            Felt::new(0),
+            Felt::new(0),
+            Felt::new(0)
+        ])
+    );
+    assert_eq!(ZoroSwapArgs::from_word(args.to_word()), args);
+    assert_eq!(ZoroSwapArgs::default().to_word(), Word::default());
+
+    // the output is a P2ID note for the creator with the next serial number
+    let payout_recipient = swap_note.payout_recipient()?;
+    assert_eq!(payout_recipient.serial_num()[3], Felt::new(5));
+    assert_eq!(
+        payout_recipient.inputs().values(),
+        [creator.suffix(), creator.prefix().as_felt()]
+    );
+    assert_eq!(swap_note.refund_recipient()?, payout_recipient);
+
+    // with another payout account, the output is tagged for and paid to that account, refunds
+    // still go to the creator
+    let payout = faucet_a;
+    let swap_note = ZoroSwapNote::with_payout(
+        creator,
+        pool,
+        offered_asset,
+        faucet_b,
+        MIN_AMOUNT_OUT,
+        BlockNumber::from(1234),
+        payout,
+        Felt::new(42),
+        serial_num,
+    )?;
+    let inputs = swap_note.note().recipient().inputs().values();
+    assert_eq!(inputs[5], Felt::from(NoteTag::from_account_id(payout)));
+    assert_eq!(inputs[6], Felt::new(42));
+    assert_eq!(inputs[7], Felt::from(NoteTag::from_account_id(creator)));
+    assert_eq!(inputs[8..10], [payout.suffix(), payout.prefix().as_felt()]);
+    assert_eq!(
+        inputs[10..12],
+        [creator.suffix(), creator.prefix().as_felt()]
+    );
+    assert_eq!(
+        swap_note.payout_recipient()?.inputs().values(),
+        [payout.suffix(), payout.prefix().as_felt()]
+    );
+    assert_eq!(
+        swap_note.refund_recipient()?.inputs().values(),
+        [creator.suffix(), creator.prefix().as_felt()]
+    );
+    assert_eq!(
+        ZoroSwapNote::from_note(swap_note.note().clone())?,
+        swap_note
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn zoro_swap_before_deadline_success() -> Result<(), Box<dyn std::error::Error>> {
+    let (outcome, balance_a, balance_b) =
+        execute_swap(MIN_AMOUNT_OUT, false, ZoroSwapArgs::default(), None).await?;
+
+    // Alice is paid AMOUNT_OUT tokens of B, the pool keeps her tokens of A
+    assert_eq!(
+        outcome,
+        ZoroSwapOutcome::Executed {
+            amount_out: AMOUNT_OUT
+        }
+    );
+    assert_eq!(balance_a, AMOUNT_IN);
+    assert_eq!(balance_b, POOL_RESERVE_OUT - AMOUNT_OUT);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn zoro_swap_after_deadline_refund() -> Result<(), Box<dyn std::error::Error>> {
+    let (outcome, balance_a, balance_b) =
+        execute_swap(MIN_AMOUNT_OUT, true, ZoroSwapArgs::default(), None).await?;
+
+    // the swap is not executed, Alice gets her tokens of A back
+    let ZoroSwapOutcome::Refunded(refund) = outcome else {
+        panic!("swap was not refunded");
+    };
+    assert_eq!(refund.unwrap_fungible().amount(), AMOUNT_IN);
+    assert_eq!(balance_a, 0);
+    assert_eq!(balance_b, POOL_RESERVE_OUT);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn zoro_swap_min_amount_out_not_met_refund() -> Result<(), Box<dyn std::error::Error>> {
+    let (outcome, balance_a, balance_b) =
+        execute_swap(AMOUNT_OUT + 1, false, ZoroSwapArgs::default(), None).await?;
+
+    let ZoroSwapOutcome::Refunded(refund) = outcome else {
+        panic!("swap was not refunded");
+    };
+    assert_eq!(refund.unwrap_fungible().amount(), AMOUNT_IN);
+    assert_eq!(balance_a, 0);
+    assert_eq!(balance_b, POOL_RESERVE_OUT);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn zoro_swap_with_expected_amount_out_success() -> Result<(), Box<dyn std::error::Error>> {
+    let args = ZoroSwapArgs {
+        expected_amount_out: Some(AMOUNT_OUT),
+    };
+    let (outcome, _, balance_b) = execute_swap(MIN_AMOUNT_OUT, false, args, None).await?;
+
+    assert_eq!(
+        outcome,
+        ZoroSwapOutcome::Executed {
+            amount_out: AMOUNT_OUT
+        }
+    );
+    assert_eq!(balance_b, POOL_RESERVE_OUT - AMOUNT_OUT);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn zoro_swap_payout_to_other_account_success() -> Result<(), Box<dyn std::error::Error>> {
+    // the proceeds land with Bob, carrying a memo in the aux field
+    let (outcome, _, balance_b) = execute_swap(
+        MIN_AMOUNT_OUT,
+        false,
+        ZoroSwapArgs::default(),
+        Some(Felt::new(42)),
+    )
+    .await?;
+
+    assert_eq!(
+        outcome,
+        ZoroSwapOutcome::Executed {
+            amount_out: AMOUNT_OUT
+        }
+    );
+    assert_eq!(balance_b, POOL_RESERVE_OUT - AMOUNT_OUT);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn zoro_swap_payout_to_other_account_refund() -> Result<(), Box<dyn std::error::Error>> {
+    // the swap misses its minimum, so the input goes back to Alice rather than to Bob
+    let (outcome, balance_a, balance_b) = execute_swap(
+        AMOUNT_OUT + 1,
+        false,
+        ZoroSwapArgs::default(),
+        Some(Felt::new(42)),
+    )
+    .await?;
+
+    let ZoroSwapOutcome::Refunded(refund) = outcome else {
+        panic!("swap was not refunded");
+    };
+    assert_eq!(refund.unwrap_fungible().amount(), AMOUNT_IN);
+    assert_eq!(balance_a, 0);
+    assert_eq!(balance_b, POOL_RESERVE_OUT);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn zoro_swap_foreign_pool_fails() -> Result<(), Box<dyn std::error::Error>> {
+    delete_keystore_and_store().await;
+
+    // -------------------------------------------------------------------------
+    // 1. Instantiate client
+    // -------------------------------------------------------------------------
+    let endpoint = if NETWORK_ID == NetworkId::Testnet {
+        Endpoint::testnet()
+    } else {
+        Endpoint::devnet()
+    };
+
+    let (mut client, keystore) = instantiate_client(endpoint).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let balances = vec![
+        vec![AMOUNT_IN, 0], // For account[0] => Alice
+    ];
+    let (accounts, faucets) =
+        setup_accounts_and_faucets(&mut client, keystore.clone(), 1, 2, balances).await?;
+
+    // rename for clarity
+    let alice_account = accounts[0].clone();
+    let faucet_a = faucets[0].clone();
+    let faucet_b = faucets[1].clone();
+
+    // two pools with the same states, both holding POOL_RESERVE_OUT tokens of B
+    let pool_state = PoolState {
+        liabilities: RESERVE_WITH_SLIPPAGE,
+        reserve: POOL_RESERVE_OUT,
+        reserve_with_slippage: RESERVE_WITH_SLIPPAGE,
+    };
+    let pools = [
+        (
+            faucet_a.id(),
+            PoolState {
+                reserve: 0,
+                ..pool_state
+            },
+        ),
+        (faucet_b.id(), pool_state),
+    ];
+    let (pool_account, _) =
+        create_zoro_pool_account(&mut client, keystore.clone(), alice_account.id(), pools).await?;
+    let (foreign_pool_account, _) =
+        create_zoro_pool_account(&mut client, keystore, alice_account.id(), pools).await?;
+    client.sync_state().await?;
+    fund_zoro_pool(
+        &mut client,
+        &faucet_b,
+        &alice_account,
+        pool_account.id(),
+        POOL_RESERVE_OUT,
+    )
+    .await?;
+    fund_zoro_pool(
+        &mut client,
+        &faucet_b,
+        &alice_account,
+        foreign_pool_account.id(),
+        POOL_RESERVE_OUT,
+    )
+    .await?;
+
+    // -------------------------------------------------------------------------
+    // 2. Alice creates a swap note for the first pool
+    // -------------------------------------------------------------------------
+    let sync_height = client.get_sync_height().await?.as_u32();
+    let serial_num = client.rng().inner_mut().draw_word();
+    let swap_note = ZoroSwapNote::new(
+        alice_account.id(),
+        pool_account.id(),
+        Asset::Fungible(FungibleAsset::new(faucet_a.id(), AMOUNT_IN).unwrap()),
+        faucet_b.id(),
+        MIN_AMOUNT_OUT,
+        BlockNumber::from(sync_height + 100),
+        serial_num,
+    )?;
+
+    let tx_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(swap_note.note().clone())])
+        .build()?;
+    let tx_exec = client
+        .new_transaction(alice_account.id(), tx_request)
+        .await?;
+    client.submit_transaction(tx_exec).await?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    // -------------------------------------------------------------------------
+    // 3. The other pool cannot execute the swap, the chosen one can
+    // -------------------------------------------------------------------------
+    let consume_req = swap_note.consume_request(ZoroSwapArgs::default())?;
+    let foreign_result = client
+        .new_transaction(foreign_pool_account.id(), consume_req.clone())
+        .await;
+    assert!(foreign_result.is_err());
+
+    let tx_exec = client
+        .new_transaction(pool_account.id(), consume_req)
+        .await?;
+    let outcome = swap_note.decode_output(tx_exec.created_notes().get_note(0))?;
+    assert_eq!(
+        outcome,
+        ZoroSwapOutcome::Executed {
+            amount_out: AMOUNT_OUT
+        }
+    );
+    client.submit_transaction(tx_exec).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn zoro_swap_unknown_asset_fails() -> Result<(), Box<dyn std::error::Error>> {
+    delete_keystore_and_store().await;
+
+    // -------------------------------------------------------------------------
+    // 1. Instantiate client
+    // -------------------------------------------------------------------------
+    let endpoint = if NETWORK_ID == NetworkId::Testnet {
+        Endpoint::testnet()
+    } else {
+        Endpoint::devnet()
+    };
+
+    let (mut client, keystore) = instantiate_client(endpoint).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let balances = vec![
+        vec![0, 0, AMOUNT_IN], // For account[0] => Alice
+    ];
+    let (accounts, faucets) =
+        setup_accounts_and_faucets(&mut client, keystore.clone(), 1, 3, balances).await?;
+
+    // rename for clarity
+    let alice_account = accounts[0].clone();
+    let faucet_a = faucets[0].clone();
+    let faucet_b = faucets[1].clone();
+    let faucet_c = faucets[2].clone();
+
+    // the pool pairs A and B and holds POOL_RESERVE_OUT tokens of B, C has no pool: the reserves
+    // of C read from storage are all zeros
+    let pool_state = PoolState {
+        liabilities: RESERVE_WITH_SLIPPAGE,
+        reserve: POOL_RESERVE_OUT,
+        reserve_with_slippage: RESERVE_WITH_SLIPPAGE,
+    };
+    let pools = [
+        (
+            faucet_a.id(),
+            PoolState {
+                reserve: 0,
+                ..pool_state
+            },
+        ),
+        (faucet_b.id(), pool_state),
+    ];
+    let (pool_account, _) =
+        create_zoro_pool_account(&mut client, keystore, alice_account.id(), pools).await?;
+    client.sync_state().await?;
+    fund_zoro_pool(
+        &mut client,
+        &faucet_b,
+        &alice_account,
+        pool_account.id(),
+        POOL_RESERVE_OUT,
+    )
+    .await?;
+    assert_eq!(
+        get_pool_assets(&client, pool_account.id()).await?,
+        [faucet_a.id(), faucet_b.id()]
+    );
+
+    // -------------------------------------------------------------------------
+    // 2. Alice offers tokens of C for B
+    // -------------------------------------------------------------------------
+    let sync_height = client.get_sync_height().await?.as_u32();
+    let serial_num = client.rng().inner_mut().draw_word();
+    let swap_note = ZoroSwapNote::new(
+        alice_account.id(),
+        pool_account.id(),
+        Asset::Fungible(FungibleAsset::new(faucet_c.id(), AMOUNT_IN).unwrap()),
+        faucet_b.id(),
+        0,
+        BlockNumber::from(sync_height + 100),
+        serial_num,
+    )?;
+
+    let tx_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(swap_note.note().clone())])
+        .build()?;
+    let tx_exec = client
+        .new_transaction(alice_account.id(), tx_request)
+        .await?;
+    client.submit_transaction(tx_exec).await?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    // -------------------------------------------------------------------------
+    // 3. The pool rejects the swap rather than paying out its whole reserve of B
+    // -------------------------------------------------------------------------
+    let consume_req = swap_note.consume_request(ZoroSwapArgs::default())?;
+    let result = client.new_transaction(pool_account.id(), consume_req).await;
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn zoro_swap_creator_cancel_success() -> Result<(), Box<dyn std::error::Error>> {
+    delete_keystore_and_store().await;
+
+    // -------------------------------------------------------------------------
+    // 1. Instantiate client
+    // -------------------------------------------------------------------------
+    let endpoint = if NETWORK_ID == NetworkId::Testnet {
+        Endpoint::testnet()
+    } else {
+        Endpoint::devnet()
+    };
+
+    let (mut client, keystore) = instantiate_client(endpoint).await.unwrap();
+    client.sync_state().await.unwrap();
+
+    let balances = vec![
+        vec![AMOUNT_IN, 0], // For account[0] => Alice
+    ];
+    let (accounts, faucets) =
+        setup_accounts_and_faucets(&mut client, keystore, 1, 2, balances).await?;
+
+    // rename for clarity
+    let alice_account = accounts[0].clone();
+    let faucet_a = faucets[0].clone();
+    let faucet_b = faucets[1].clone();
+
+    // -------------------------------------------------------------------------
+    // 2. Alice creates the swap note
+    // -------------------------------------------------------------------------
+    let sync_height = client.get_sync_height().await?.as_u32();
+    let serial_num = client.rng().inner_mut().draw_word();
+    // no pool consumes the note before it is cancelled
+    let pool = AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE)?;
+    let swap_note = ZoroSwapNote::new(
+        alice_account.id(),
+        pool,
+        Asset::Fungible(FungibleAsset::new(faucet_a.id(), AMOUNT_IN).unwrap()),
+        faucet_b.id(),
+        MIN_AMOUNT_OUT,
+        BlockNumber::from(sync_height + 100),
+        serial_num,
+    )?;
+
+    let tx_request = TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(swap_note.note().clone())])
+        .build()
+        .unwrap();
+    let tx_exec = client
+        .new_transaction(alice_account.id(), tx_request)
+        .await?;
+    client.submit_transaction(tx_exec).await?;
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    // -------------------------------------------------------------------------
+    // 3. Alice finds her pending swap in the store and cancels it
+    // -------------------------------------------------------------------------
+    let outstanding = find_outstanding_zoro_swaps(&client, alice_account.id()).await?;
+    assert_eq!(outstanding, vec![swap_note.clone()]);
+
+    let tx_exec = client
+        .new_transaction(alice_account.id(), outstanding[0].cancel_request()?)
+        .await?;
+    client.submit_transaction(tx_exec.clone()).await?;
+    assert_eq!(tx_exec.created_notes().num_notes(), 0);
+
+    sleep(Duration::from_secs(SYNC_STATE_WAIT_TIME)).await;
+    client.sync_state().await?;
+
+    let alice_account_state = client
+        .get_account(alice_account.id())
+        .await?
+        .expect("alice account not found");
+    let balance_a = alice_account_state
+        .account()
+        .vault()
+        .get_balance(faucet_a.id())
+        .unwrap();
+    assert_eq!(balance_a, AMOUNT_IN);
+    assert!(
+        find_outstanding_zoro_swaps(&client, alice_account.id())
+            .await?
+            .is_empty()
+    );
+
+    Ok(())
+}